@@ -1,6 +1,7 @@
+use crate::buf::{lines, search, take, varint, Chain, Lines, Take, VarintError};
 #[cfg(feature = "std")]
 use crate::buf::{reader, Reader};
-use crate::buf::{take, Chain, Take};
+use crate::TryGetError;
 
 use core::{cmp, mem, ptr};
 
@@ -8,6 +9,8 @@ use core::{cmp, mem, ptr};
 use std::io::IoSlice;
 
 use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
 
 macro_rules! buf_get_impl {
     ($this:ident, $typ:tt::$conv:tt) => {{
@@ -49,6 +52,117 @@ macro_rules! buf_get_impl {
     }};
 }
 
+macro_rules! buf_peek_impl {
+    ($this:ident, $typ:tt::$conv:tt) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+        // same fast path as `buf_get_impl!`, minus the advance
+        if let Some(src) = $this.chunk().get(..SIZE) {
+            return Ok(unsafe { $typ::$conv(*(src as *const _ as *const [_; SIZE])) });
+        }
+
+        let mut buf = [0; SIZE];
+        $this.peek_slice(&mut buf)?;
+        return Ok($typ::$conv(buf));
+    }};
+    (le => $this:ident, $typ:tt, $len_to_read:expr) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        if $len_to_read > SIZE {
+            panic!("`nbytes` greater than {}", SIZE);
+        }
+
+        let mut buf = [0; SIZE];
+        $this.peek_slice(&mut buf[..($len_to_read)])?;
+        return Ok($typ::from_le_bytes(buf));
+    }};
+    (be => $this:ident, $typ:tt, $len_to_read:expr) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        let slice_at = match SIZE.checked_sub($len_to_read) {
+            Some(slice_at) => slice_at,
+            None => panic!("`nbytes` greater than {}", SIZE),
+        };
+
+        let mut buf = [0; SIZE];
+        $this.peek_slice(&mut buf[slice_at..])?;
+        return Ok($typ::from_be_bytes(buf));
+    }};
+}
+
+macro_rules! buf_try_get_impl {
+    ($this:ident, $typ:tt::$conv:tt) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        if $this.remaining() < SIZE {
+            return Err(TryGetError {
+                requested: SIZE,
+                available: $this.remaining(),
+            });
+        }
+
+        // same fast path as `buf_get_impl!`, the length check above
+        // guarantees that the slow path cannot panic
+        let ret = $this
+            .chunk()
+            .get(..SIZE)
+            .map(|src| unsafe { $typ::$conv(*(src as *const _ as *const [_; SIZE])) });
+
+        if let Some(ret) = ret {
+            $this.advance(SIZE);
+            return Ok(ret);
+        } else {
+            let mut buf = [0; SIZE];
+            $this.copy_to_slice(&mut buf);
+            return Ok($typ::$conv(buf));
+        }
+    }};
+    (le => $this:ident, $typ:tt, $len_to_read:expr) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        let mut buf = [0; SIZE];
+        $this.try_copy_to_slice(&mut buf[..($len_to_read)])?;
+        return Ok($typ::from_le_bytes(buf));
+    }};
+    (be => $this:ident, $typ:tt, $len_to_read:expr) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        let slice_at = match SIZE.checked_sub($len_to_read) {
+            Some(slice_at) => slice_at,
+            None => panic!("`nbytes` greater than {}", SIZE),
+        };
+
+        let mut buf = [0; SIZE];
+        $this.try_copy_to_slice(&mut buf[slice_at..])?;
+        return Ok($typ::from_be_bytes(buf));
+    }};
+}
+
+macro_rules! buf_try_get_prefixed_impl {
+    ($this:ident, $typ:tt::$peek:ident) => {{
+        const SIZE: usize = mem::size_of::<$typ>();
+
+        if $this.remaining() < SIZE {
+            return Err(TryGetError {
+                requested: SIZE,
+                available: $this.remaining(),
+            });
+        }
+
+        let len = u64::from($this.$peek()?);
+        let available = $this.remaining() - SIZE;
+
+        if len > available as u64 {
+            return Err(TryGetError {
+                requested: (cmp::min(len, core::usize::MAX as u64) as usize).saturating_add(SIZE),
+                available: $this.remaining(),
+            });
+        }
+
+        $this.advance(SIZE);
+        return Ok($this.copy_to_bytes(len as usize));
+    }};
+}
+
 /// Read bytes from a buffer.
 ///
 /// A buffer stores bytes in memory such that read operations are infallible.
@@ -173,6 +287,72 @@ pub trait Buf {
         }
     }
 
+    /// Calls `f` with each of the chunks making up the remaining bytes of
+    /// `self`, in order, until `f` returns `false`.
+    ///
+    /// Unlike repeatedly calling `chunk()` and `advance()`, this does not
+    /// consume any data. It is the building block of the methods that look
+    /// ahead in the buffer, such as `peek_slice`, `find_byte` and `find_slice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = (&b"hello "[..]).chain(&b"world"[..]);
+    /// let mut chunks = vec![];
+    ///
+    /// buf.for_each_chunk(&mut |chunk| {
+    ///     chunks.push(chunk.to_vec());
+    ///     true
+    /// });
+    ///
+    /// assert_eq!(chunks, [&b"hello "[..], &b"world"[..]]);
+    /// ```
+    ///
+    /// # Implementer notes
+    ///
+    /// The default implementation visits `chunk()` if it holds all of the
+    /// remaining bytes, and otherwise all of the slices `chunks_vectored`
+    /// fills in, or still only `chunk()` without the `std` feature. These may
+    /// not cover all of the remaining bytes, in which case the methods built
+    /// on `for_each_chunk` report that they could not look at the others:
+    /// `peek_slice`, `find_byte` and `find_slice` return an error, and
+    /// `lines` panics. Buffers whose data is not contiguous should override
+    /// this method.
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        let chunk = self.chunk();
+
+        #[cfg(feature = "std")]
+        {
+            if chunk.len() < self.remaining() {
+                let mut slices = Vec::new();
+                let mut n;
+
+                // Grow `slices` until `chunks_vectored` leaves some of them
+                // unused or they cover all of the remaining bytes.
+                loop {
+                    slices.resize(cmp::max(16, slices.len() * 4), IoSlice::new(&[]));
+                    n = self.chunks_vectored(&mut slices);
+
+                    let len = slices[..n].iter().map(|s| s.len()).sum::<usize>();
+                    if n < slices.len() || len >= self.remaining() {
+                        break;
+                    }
+                }
+
+                for slice in &slices[..n] {
+                    if !f(slice) {
+                        return;
+                    }
+                }
+                return;
+            }
+        }
+
+        f(chunk);
+    }
+
     /// Advance the internal cursor of the Buf
     ///
     /// The next call to `chunk()` will return a slice starting `cnt` bytes
@@ -798,130 +978,2047 @@ pub trait Buf {
         f64::from_bits(Self::get_u64_le(self))
     }
 
-    /// Consumes `len` bytes inside self and returns new instance of `Bytes`
-    /// with this data.
+    /// Copies bytes from `self` into `dst`.
     ///
-    /// This function may be optimized by the underlying type to avoid actual
-    /// copies. For example, `Bytes` implementation will do a shallow copy
-    /// (ref-count increment).
+    /// The cursor is advanced by the number of bytes copied. `self` must have
+    /// enough remaining bytes to fill `dst`.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// fill `dst`, in which case neither `self` nor `dst` is modified.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::Buf;
+    /// use bytes::{Buf, TryGetError};
     ///
-    /// let bytes = (&b"hello world"[..]).copy_to_bytes(5);
-    /// assert_eq!(&bytes[..], &b"hello"[..]);
+    /// let mut buf = &b"hello world"[..];
+    /// let mut dst = [0; 5];
+    ///
+    /// assert_eq!(Ok(()), buf.try_copy_to_slice(&mut dst));
+    /// assert_eq!(&b"hello"[..], &dst);
+    /// assert_eq!(6, buf.remaining());
+    ///
+    /// let mut dst = [0; 8];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 6 }), buf.try_copy_to_slice(&mut dst));
+    /// assert_eq!(6, buf.remaining());
     /// ```
-    fn copy_to_bytes(&mut self, len: usize) -> crate::Bytes {
-        use super::BufMut;
+    fn try_copy_to_slice(&mut self, dst: &mut [u8]) -> Result<(), TryGetError> {
+        if self.remaining() < dst.len() {
+            return Err(TryGetError {
+                requested: dst.len(),
+                available: self.remaining(),
+            });
+        }
 
-        assert!(len <= self.remaining(), "`len` greater than remaining");
+        self.copy_to_slice(dst);
+        Ok(())
+    }
 
-        let mut ret = crate::BytesMut::with_capacity(len);
-        ret.put(self.take(len));
-        ret.freeze()
+    /// Gets an unsigned 8 bit integer from `self`.
+    ///
+    /// The current position is advanced by 1.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08 hello"[..];
+    /// assert_eq!(Ok(0x08_u8), buf.try_get_u8());
+    ///
+    /// let mut buf = &b""[..];
+    /// assert_eq!(Err(TryGetError { requested: 1, available: 0 }), buf.try_get_u8());
+    /// assert_eq!(0, buf.remaining());
+    /// ```
+    fn try_get_u8(&mut self) -> Result<u8, TryGetError> {
+        if self.remaining() < 1 {
+            return Err(TryGetError {
+                requested: 1,
+                available: self.remaining(),
+            });
+        }
+        let ret = self.chunk()[0];
+        self.advance(1);
+        Ok(ret)
     }
 
-    /// Creates an adaptor which will read at most `limit` bytes from `self`.
+    /// Gets a signed 8 bit integer from `self`.
     ///
-    /// This function returns a new instance of `Buf` which will read at most
-    /// `limit` bytes.
+    /// The current position is advanced by 1.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::{Buf, BufMut};
+    /// use bytes::{Buf, TryGetError};
     ///
-    /// let mut buf = b"hello world"[..].take(5);
-    /// let mut dst = vec![];
+    /// let mut buf = &b"\x08 hello"[..];
+    /// assert_eq!(Ok(0x08_i8), buf.try_get_i8());
+    ///
+    /// let mut buf = &b""[..];
+    /// assert_eq!(Err(TryGetError { requested: 1, available: 0 }), buf.try_get_i8());
+    /// assert_eq!(0, buf.remaining());
+    /// ```
+    fn try_get_i8(&mut self) -> Result<i8, TryGetError> {
+        if self.remaining() < 1 {
+            return Err(TryGetError {
+                requested: 1,
+                available: self.remaining(),
+            });
+        }
+        let ret = self.chunk()[0] as i8;
+        self.advance(1);
+        Ok(ret)
+    }
+
+    /// Gets an unsigned 16 bit integer from `self` in big-endian byte order.
     ///
-    /// dst.put(&mut buf);
-    /// assert_eq!(dst, b"hello");
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
     ///
-    /// let mut buf = buf.into_inner();
-    /// dst.clear();
-    /// dst.put(&mut buf);
-    /// assert_eq!(dst, b" world");
     /// ```
-    fn take(self, limit: usize) -> Take<Self>
-    where
-        Self: Sized,
-    {
-        take::new(self, limit)
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08\x09 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.try_get_u16());
+    ///
+    /// let mut buf = &b"\x01"[..];
+    /// assert_eq!(Err(TryGetError { requested: 2, available: 1 }), buf.try_get_u16());
+    /// assert_eq!(1, buf.remaining());
+    /// ```
+    fn try_get_u16(&mut self) -> Result<u16, TryGetError> {
+        buf_try_get_impl!(self, u16::from_be_bytes);
     }
 
-    /// Creates an adaptor which will chain this buffer with another.
+    /// Gets an unsigned 16 bit integer from `self` in little-endian byte order.
     ///
-    /// The returned `Buf` instance will first consume all bytes from `self`.
-    /// Afterwards the output is equivalent to the output of next.
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::Buf;
+    /// use bytes::{Buf, TryGetError};
     ///
-    /// let mut chain = b"hello "[..].chain(&b"world"[..]);
+    /// let mut buf = &b"\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.try_get_u16_le());
     ///
-    /// let full = chain.copy_to_bytes(11);
-    /// assert_eq!(full.chunk(), b"hello world");
+    /// let mut buf = &b"\x01"[..];
+    /// assert_eq!(Err(TryGetError { requested: 2, available: 1 }), buf.try_get_u16_le());
+    /// assert_eq!(1, buf.remaining());
     /// ```
-    fn chain<U: Buf>(self, next: U) -> Chain<Self, U>
-    where
-        Self: Sized,
-    {
-        Chain::new(self, next)
+    fn try_get_u16_le(&mut self) -> Result<u16, TryGetError> {
+        buf_try_get_impl!(self, u16::from_le_bytes);
     }
 
-    /// Creates an adaptor which implements the `Read` trait for `self`.
+    /// Gets a signed 16 bit integer from `self` in big-endian byte order.
     ///
-    /// This function returns a new value which implements `Read` by adapting
-    /// the `Read` trait functions to the `Buf` trait functions. Given that
-    /// `Buf` operations are infallible, none of the `Read` functions will
-    /// return with `Err`.
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::{Bytes, Buf};
-    /// use std::io::Read;
+    /// use bytes::{Buf, TryGetError};
     ///
-    /// let buf = Bytes::from("hello world");
+    /// let mut buf = &b"\x08\x09 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.try_get_i16());
     ///
-    /// let mut reader = buf.reader();
-    /// let mut dst = [0; 1024];
+    /// let mut buf = &b"\x01"[..];
+    /// assert_eq!(Err(TryGetError { requested: 2, available: 1 }), buf.try_get_i16());
+    /// assert_eq!(1, buf.remaining());
+    /// ```
+    fn try_get_i16(&mut self) -> Result<i16, TryGetError> {
+        buf_try_get_impl!(self, i16::from_be_bytes);
+    }
+
+    /// Gets a signed 16 bit integer from `self` in little-endian byte order.
     ///
-    /// let num = reader.read(&mut dst).unwrap();
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(11, num);
-    /// assert_eq!(&dst[..11], &b"hello world"[..]);
     /// ```
-    #[cfg(feature = "std")]
-    fn reader(self) -> Reader<Self>
-    where
-        Self: Sized,
-    {
-        reader::new(self)
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.try_get_i16_le());
+    ///
+    /// let mut buf = &b"\x01"[..];
+    /// assert_eq!(Err(TryGetError { requested: 2, available: 1 }), buf.try_get_i16_le());
+    /// assert_eq!(1, buf.remaining());
+    /// ```
+    fn try_get_i16_le(&mut self) -> Result<i16, TryGetError> {
+        buf_try_get_impl!(self, i16::from_le_bytes);
     }
-}
 
-macro_rules! deref_forward_buf {
-    () => {
-        fn remaining(&self) -> usize {
-            (**self).remaining()
-        }
+    /// Gets an unsigned 32 bit integer from `self` in the big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08\x09\xA0\xA1 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.try_get_u32());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_u32());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_u32(&mut self) -> Result<u32, TryGetError> {
+        buf_try_get_impl!(self, u32::from_be_bytes);
+    }
 
-        fn chunk(&self) -> &[u8] {
-            (**self).chunk()
-        }
+    /// Gets an unsigned 32 bit integer from `self` in the little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\xA1\xA0\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.try_get_u32_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_u32_le());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_u32_le(&mut self) -> Result<u32, TryGetError> {
+        buf_try_get_impl!(self, u32::from_le_bytes);
+    }
 
-        #[cfg(feature = "std")]
-        fn chunks_vectored<'b>(&'b self, dst: &mut [IoSlice<'b>]) -> usize {
-            (**self).chunks_vectored(dst)
-        }
+    /// Gets a signed 32 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08\x09\xA0\xA1 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.try_get_i32());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_i32());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_i32(&mut self) -> Result<i32, TryGetError> {
+        buf_try_get_impl!(self, i32::from_be_bytes);
+    }
 
-        fn advance(&mut self, cnt: usize) {
-            (**self).advance(cnt)
+    /// Gets a signed 32 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\xA1\xA0\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.try_get_i32_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_i32_le());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_i32_le(&mut self) -> Result<i32, TryGetError> {
+        buf_try_get_impl!(self, i32::from_le_bytes);
+    }
+
+    /// Gets an unsigned 64 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.try_get_u64());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_u64());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_u64(&mut self) -> Result<u64, TryGetError> {
+        buf_try_get_impl!(self, u64::from_be_bytes);
+    }
+
+    /// Gets an unsigned 64 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.try_get_u64_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_u64_le());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_u64_le(&mut self) -> Result<u64, TryGetError> {
+        buf_try_get_impl!(self, u64::from_le_bytes);
+    }
+
+    /// Gets a signed 64 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.try_get_i64());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_i64());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_i64(&mut self) -> Result<i64, TryGetError> {
+        buf_try_get_impl!(self, i64::from_be_bytes);
+    }
+
+    /// Gets a signed 64 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.try_get_i64_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_i64_le());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_i64_le(&mut self) -> Result<i64, TryGetError> {
+        buf_try_get_impl!(self, i64::from_le_bytes);
+    }
+
+    /// Gets an unsigned 128 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.try_get_u128());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_u128());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_u128(&mut self) -> Result<u128, TryGetError> {
+        buf_try_get_impl!(self, u128::from_be_bytes);
+    }
+
+    /// Gets an unsigned 128 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.try_get_u128_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_u128_le());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_u128_le(&mut self) -> Result<u128, TryGetError> {
+        buf_try_get_impl!(self, u128::from_le_bytes);
+    }
+
+    /// Gets a signed 128 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.try_get_i128());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_i128());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_i128(&mut self) -> Result<i128, TryGetError> {
+        buf_try_get_impl!(self, i128::from_be_bytes);
+    }
+
+    /// Gets a signed 128 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.try_get_i128_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0A\x0B\x0C\x0D\x0E\x0F"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_i128_le());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_i128_le(&mut self) -> Result<i128, TryGetError> {
+        buf_try_get_impl!(self, i128::from_le_bytes);
+    }
+
+    /// Gets an unsigned n-byte integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.try_get_uint(3));
+    ///
+    /// let mut buf = &b"\x01\x02"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 2 }), buf.try_get_uint(4));
+    /// assert_eq!(2, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    fn try_get_uint(&mut self, nbytes: usize) -> Result<u64, TryGetError> {
+        buf_try_get_impl!(be => self, u64, nbytes);
+    }
+
+    /// Gets an unsigned n-byte integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.try_get_uint_le(3));
+    ///
+    /// let mut buf = &b"\x01\x02"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 2 }), buf.try_get_uint_le(4));
+    /// assert_eq!(2, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    fn try_get_uint_le(&mut self, nbytes: usize) -> Result<u64, TryGetError> {
+        buf_try_get_impl!(le => self, u64, nbytes);
+    }
+
+    /// Gets a signed n-byte integer from `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x01\x02\x03 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.try_get_int(3));
+    ///
+    /// let mut buf = &b"\x01\x02"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 2 }), buf.try_get_int(4));
+    /// assert_eq!(2, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    fn try_get_int(&mut self, nbytes: usize) -> Result<i64, TryGetError> {
+        buf_try_get_impl!(be => self, i64, nbytes);
+    }
+
+    /// Gets a signed n-byte integer from `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.try_get_int_le(3));
+    ///
+    /// let mut buf = &b"\x01\x02"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 2 }), buf.try_get_int_le(4));
+    /// assert_eq!(2, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    fn try_get_int_le(&mut self, nbytes: usize) -> Result<i64, TryGetError> {
+        buf_try_get_impl!(le => self, i64, nbytes);
+    }
+
+    /// Gets an IEEE754 single-precision (4 bytes) floating point number from
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x3F\x99\x99\x9A hello"[..];
+    /// assert_eq!(Ok(1.2f32), buf.try_get_f32());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_f32());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_f32(&mut self) -> Result<f32, TryGetError> {
+        Ok(f32::from_bits(self.try_get_u32()?))
+    }
+
+    /// Gets an IEEE754 single-precision (4 bytes) floating point number from
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x9A\x99\x99\x3F hello"[..];
+    /// assert_eq!(Ok(1.2f32), buf.try_get_f32_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.try_get_f32_le());
+    /// assert_eq!(3, buf.remaining());
+    /// ```
+    fn try_get_f32_le(&mut self) -> Result<f32, TryGetError> {
+        Ok(f32::from_bits(self.try_get_u32_le()?))
+    }
+
+    /// Gets an IEEE754 double-precision (8 bytes) floating point number from
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x3F\xF3\x33\x33\x33\x33\x33\x33 hello"[..];
+    /// assert_eq!(Ok(1.2f64), buf.try_get_f64());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_f64());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_f64(&mut self) -> Result<f64, TryGetError> {
+        Ok(f64::from_bits(self.try_get_u64()?))
+    }
+
+    /// Gets an IEEE754 double-precision (8 bytes) floating point number from
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// read the value, in which case the current position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let mut buf = &b"\x33\x33\x33\x33\x33\x33\xF3\x3F hello"[..];
+    /// assert_eq!(Ok(1.2f64), buf.try_get_f64_le());
+    ///
+    /// let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07"[..];
+    /// assert_eq!(Err(TryGetError { requested: 8, available: 7 }), buf.try_get_f64_le());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    fn try_get_f64_le(&mut self) -> Result<f64, TryGetError> {
+        Ok(f64::from_bits(self.try_get_u64_le()?))
+    }
+
+    /// Copies bytes from `self` into `dst` without advancing the current
+    /// position.
+    ///
+    /// Unlike `chunk()`, the copied bytes may span several chunks. They are
+    /// collected with [`for_each_chunk`].
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes to
+    /// fill `dst`, or when some of them cannot be reached without advancing
+    /// because `self` does not override `for_each_chunk`. `available` is then
+    /// the number of bytes that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let buf = (&b"hel"[..]).chain(&b"lo world"[..]);
+    /// let mut dst = [0; 5];
+    ///
+    /// assert_eq!(Ok(()), buf.peek_slice(&mut dst));
+    /// assert_eq!(&b"hello"[..], &dst);
+    /// assert_eq!(11, buf.remaining());
+    ///
+    /// let mut dst = [0; 12];
+    /// assert_eq!(Err(TryGetError { requested: 12, available: 11 }), buf.peek_slice(&mut dst));
+    /// ```
+    ///
+    /// [`for_each_chunk`]: #method.for_each_chunk
+    fn peek_slice(&self, dst: &mut [u8]) -> Result<(), TryGetError> {
+        if self.remaining() < dst.len() {
+            return Err(TryGetError {
+                requested: dst.len(),
+                available: self.remaining(),
+            });
+        }
+
+        let mut off = 0;
+        self.for_each_chunk(&mut |src| {
+            let cnt = cmp::min(src.len(), dst.len() - off);
+            dst[off..off + cnt].copy_from_slice(&src[..cnt]);
+            off += cnt;
+            off < dst.len()
+        });
+
+        if off < dst.len() {
+            return Err(TryGetError {
+                requested: dst.len(),
+                available: off,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Reads an unsigned 8 bit integer from `self`.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08 hello"[..];
+    /// assert_eq!(Ok(8), buf.peek_u8());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u8(&self) -> Result<u8, TryGetError> {
+        buf_peek_impl!(self, u8::from_be_bytes);
+    }
+
+    /// Reads a signed 8 bit integer from `self`.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08 hello"[..];
+    /// assert_eq!(Ok(8), buf.peek_i8());
+    /// assert_eq!(7, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i8(&self) -> Result<i8, TryGetError> {
+        buf_peek_impl!(self, i8::from_be_bytes);
+    }
+
+    /// Reads an unsigned 16 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08\x09 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.peek_u16());
+    /// assert_eq!(8, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u16(&self) -> Result<u16, TryGetError> {
+        buf_peek_impl!(self, u16::from_be_bytes);
+    }
+
+    /// Reads an unsigned 16 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.peek_u16_le());
+    /// assert_eq!(8, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u16_le(&self) -> Result<u16, TryGetError> {
+        buf_peek_impl!(self, u16::from_le_bytes);
+    }
+
+    /// Reads a signed 16 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08\x09 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.peek_i16());
+    /// assert_eq!(8, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i16(&self) -> Result<i16, TryGetError> {
+        buf_peek_impl!(self, i16::from_be_bytes);
+    }
+
+    /// Reads a signed 16 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809), buf.peek_i16_le());
+    /// assert_eq!(8, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i16_le(&self) -> Result<i16, TryGetError> {
+        buf_peek_impl!(self, i16::from_le_bytes);
+    }
+
+    /// Reads an unsigned 32 bit integer from `self` in the big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, TryGetError};
+    ///
+    /// let buf = &b"\x08\x09\xA0\xA1 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.peek_u32());
+    /// assert_eq!(10, buf.remaining());
+    ///
+    /// let buf = &b"\x08\x09\xA0"[..];
+    /// assert_eq!(Err(TryGetError { requested: 4, available: 3 }), buf.peek_u32());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u32(&self) -> Result<u32, TryGetError> {
+        buf_peek_impl!(self, u32::from_be_bytes);
+    }
+
+    /// Reads an unsigned 32 bit integer from `self` in the little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\xA1\xA0\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.peek_u32_le());
+    /// assert_eq!(10, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u32_le(&self) -> Result<u32, TryGetError> {
+        buf_peek_impl!(self, u32::from_le_bytes);
+    }
+
+    /// Reads a signed 32 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08\x09\xA0\xA1 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.peek_i32());
+    /// assert_eq!(10, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i32(&self) -> Result<i32, TryGetError> {
+        buf_peek_impl!(self, i32::from_be_bytes);
+    }
+
+    /// Reads a signed 32 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\xA1\xA0\x09\x08 hello"[..];
+    /// assert_eq!(Ok(0x0809A0A1), buf.peek_i32_le());
+    /// assert_eq!(10, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i32_le(&self) -> Result<i32, TryGetError> {
+        buf_peek_impl!(self, i32::from_le_bytes);
+    }
+
+    /// Reads an unsigned 64 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.peek_u64());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u64(&self) -> Result<u64, TryGetError> {
+        buf_peek_impl!(self, u64::from_be_bytes);
+    }
+
+    /// Reads an unsigned 64 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.peek_u64_le());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u64_le(&self) -> Result<u64, TryGetError> {
+        buf_peek_impl!(self, u64::from_le_bytes);
+    }
+
+    /// Reads a signed 64 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.peek_i64());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i64(&self) -> Result<i64, TryGetError> {
+        buf_peek_impl!(self, i64::from_be_bytes);
+    }
+
+    /// Reads a signed 64 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x0102030405060708), buf.peek_i64_le());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i64_le(&self) -> Result<i64, TryGetError> {
+        buf_peek_impl!(self, i64::from_le_bytes);
+    }
+
+    /// Reads an unsigned 128 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.peek_u128());
+    /// assert_eq!(22, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u128(&self) -> Result<u128, TryGetError> {
+        buf_peek_impl!(self, u128::from_be_bytes);
+    }
+
+    /// Reads an unsigned 128 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.peek_u128_le());
+    /// assert_eq!(22, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_u128_le(&self) -> Result<u128, TryGetError> {
+        buf_peek_impl!(self, u128::from_le_bytes);
+    }
+
+    /// Reads a signed 128 bit integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.peek_i128());
+    /// assert_eq!(22, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i128(&self) -> Result<i128, TryGetError> {
+        buf_peek_impl!(self, i128::from_be_bytes);
+    }
+
+    /// Reads a signed 128 bit integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x01020304050607080910111213141516), buf.peek_i128_le());
+    /// assert_eq!(22, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_i128_le(&self) -> Result<i128, TryGetError> {
+        buf_peek_impl!(self, i128::from_le_bytes);
+    }
+
+    /// Reads an unsigned n-byte integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.peek_uint(3));
+    /// assert_eq!(9, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_uint(&self, nbytes: usize) -> Result<u64, TryGetError> {
+        buf_peek_impl!(be => self, u64, nbytes);
+    }
+
+    /// Reads an unsigned n-byte integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.peek_uint_le(3));
+    /// assert_eq!(9, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_uint_le(&self, nbytes: usize) -> Result<u64, TryGetError> {
+        buf_peek_impl!(le => self, u64, nbytes);
+    }
+
+    /// Reads a signed n-byte integer from `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x01\x02\x03 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.peek_int(3));
+    /// assert_eq!(9, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_int(&self, nbytes: usize) -> Result<i64, TryGetError> {
+        buf_peek_impl!(be => self, i64, nbytes);
+    }
+
+    /// Reads a signed n-byte integer from `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x03\x02\x01 hello"[..];
+    /// assert_eq!(Ok(0x010203), buf.peek_int_le(3));
+    /// assert_eq!(9, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` > 8.
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_int_le(&self, nbytes: usize) -> Result<i64, TryGetError> {
+        buf_peek_impl!(le => self, i64, nbytes);
+    }
+
+    /// Reads an IEEE754 single-precision (4 bytes) floating point number from
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x3F\x99\x99\x9A hello"[..];
+    /// assert_eq!(Ok(1.2f32), buf.peek_f32());
+    /// assert_eq!(10, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_f32(&self) -> Result<f32, TryGetError> {
+        Ok(f32::from_bits(Self::peek_u32(self)?))
+    }
+
+    /// Reads an IEEE754 single-precision (4 bytes) floating point number from
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x9A\x99\x99\x3F hello"[..];
+    /// assert_eq!(Ok(1.2f32), buf.peek_f32_le());
+    /// assert_eq!(10, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_f32_le(&self) -> Result<f32, TryGetError> {
+        Ok(f32::from_bits(Self::peek_u32_le(self)?))
+    }
+
+    /// Reads an IEEE754 double-precision (8 bytes) floating point number from
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x3F\xF3\x33\x33\x33\x33\x33\x33 hello"[..];
+    /// assert_eq!(Ok(1.2f64), buf.peek_f64());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_f64(&self) -> Result<f64, TryGetError> {
+        Ok(f64::from_bits(Self::peek_u64(self)?))
+    }
+
+    /// Reads an IEEE754 double-precision (8 bytes) floating point number from
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is not advanced.
+    ///
+    /// Returns `Err(TryGetError)` when the value cannot be read, see
+    /// [`peek_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = &b"\x33\x33\x33\x33\x33\x33\xF3\x3F hello"[..];
+    /// assert_eq!(Ok(1.2f64), buf.peek_f64_le());
+    /// assert_eq!(14, buf.remaining());
+    /// ```
+    ///
+    /// [`peek_slice`]: #method.peek_slice
+    fn peek_f64_le(&self) -> Result<f64, TryGetError> {
+        Ok(f64::from_bits(Self::peek_u64_le(self)?))
+    }
+
+    /// Gets an unsigned LEB128 varint from `self`, as used by protobuf and
+    /// WebAssembly.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is at most 10 bytes. The varint may span several chunks.
+    ///
+    /// Returns `Err(VarintError::Truncated)` when `self` ends before the last
+    /// byte of the varint and `Err(VarintError::Overflow)` when the value does
+    /// not fit in a `u64`. The current position is left unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let mut buf = &b"\xAC\x02 hello"[..];
+    /// assert_eq!(Ok(300), buf.get_uvarint_u64());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    fn get_uvarint_u64(&mut self) -> Result<u64, VarintError> {
+        varint::get_uvarint(self, varint::MAX_LEN_U64, 0x01)
+    }
+
+    /// Gets an unsigned LEB128 varint from `self` that fits in a `u32`.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is at most 5 bytes.
+    ///
+    /// Returns `Err(VarintError::Truncated)` when `self` ends before the last
+    /// byte of the varint and `Err(VarintError::Overflow)` when the value does
+    /// not fit in a `u32`. The current position is left unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    /// use bytes::buf::VarintError;
+    ///
+    /// let mut buf = &b"\xFF\xFF\xFF\xFF\x0F"[..];
+    /// assert_eq!(Ok(std::u32::MAX), buf.get_uvarint_u32());
+    ///
+    /// let mut buf = &b"\x80\x80\x80\x80\x10"[..];
+    /// assert_eq!(Err(VarintError::Overflow), buf.get_uvarint_u32());
+    /// assert_eq!(5, buf.remaining());
+    /// ```
+    fn get_uvarint_u32(&mut self) -> Result<u32, VarintError> {
+        varint::get_uvarint(self, varint::MAX_LEN_U32, 0x0f).map(|n| n as u32)
+    }
+
+    /// Gets a signed, zigzag encoded LEB128 varint from `self`, as used by the
+    /// protobuf `sint64` type.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is at most 10 bytes.
+    ///
+    /// Returns the same errors as `get_uvarint_u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let mut buf = &b"\x03\x04 hello"[..];
+    /// assert_eq!(Ok(-2), buf.get_ivarint_i64());
+    /// assert_eq!(Ok(2), buf.get_ivarint_i64());
+    /// ```
+    fn get_ivarint_i64(&mut self) -> Result<i64, VarintError> {
+        self.get_uvarint_u64().map(varint::zigzag_decode)
+    }
+
+    /// Gets a signed, zigzag encoded LEB128 varint from `self` that fits in
+    /// an `i32`, as used by the protobuf `sint32` type.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is at most 5 bytes.
+    ///
+    /// Returns the same errors as `get_uvarint_u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let mut buf = &b"\xFF\xFF\xFF\xFF\x0F"[..];
+    /// assert_eq!(Ok(std::i32::MIN), buf.get_ivarint_i32());
+    /// ```
+    fn get_ivarint_i32(&mut self) -> Result<i32, VarintError> {
+        self.get_uvarint_u32()
+            .map(|n| varint::zigzag_decode(u64::from(n)) as i32)
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 8 bit integer.
+    ///
+    /// The current position is advanced by 1 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x05hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u8_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u8_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u8_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 8 bit integer.
+    ///
+    /// The current position is advanced by 1 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x05hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u8_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x0Chello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 13, available: 12 }), buf.try_get_u8_prefixed_bytes());
+    /// assert_eq!(12, buf.remaining());
+    /// ```
+    fn try_get_u8_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u8::peek_u8);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 16 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 2 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x00\x05hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u16_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u16_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u16_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 16 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 2 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x00\x05hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u16_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x00\x0Chello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 14, available: 13 }), buf.try_get_u16_prefixed_bytes());
+    /// assert_eq!(13, buf.remaining());
+    /// ```
+    fn try_get_u16_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u16::peek_u16);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 16 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 2 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x05\x00hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u16_le_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u16_le_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u16_le_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 16 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 2 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x05\x00hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u16_le_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x0C\x00hello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 14, available: 13 }), buf.try_get_u16_le_prefixed_bytes());
+    /// assert_eq!(13, buf.remaining());
+    /// ```
+    fn try_get_u16_le_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u16::peek_u16_le);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 32 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 4 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x00\x00\x00\x05hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u32_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u32_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u32_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 32 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 4 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x00\x00\x00\x05hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u32_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x00\x00\x00\x0Chello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_u32_prefixed_bytes());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_u32_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u32::peek_u32);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 32 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 4 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x05\x00\x00\x00hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u32_le_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u32_le_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u32_le_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 32 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 4 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x05\x00\x00\x00hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u32_le_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x0C\x00\x00\x00hello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 16, available: 15 }), buf.try_get_u32_le_prefixed_bytes());
+    /// assert_eq!(15, buf.remaining());
+    /// ```
+    fn try_get_u32_le_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u32::peek_u32_le);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 64 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 8 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x00\x00\x00\x00\x00\x00\x00\x05hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u64_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u64_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u64_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 64 bit integer in
+    /// big-endian byte order.
+    ///
+    /// The current position is advanced by 8 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x00\x00\x00\x00\x00\x00\x00\x05hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u64_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x00\x00\x00\x00\x00\x00\x00\x0Chello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 20, available: 19 }), buf.try_get_u64_prefixed_bytes());
+    /// assert_eq!(19, buf.remaining());
+    /// ```
+    fn try_get_u64_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u64::peek_u64);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 64 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 8 plus the length. The bytes are
+    /// obtained through `copy_to_bytes`, so no copy is made when `self` is a
+    /// `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x05\x00\x00\x00\x00\x00\x00\x00hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_u64_le_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining data in `self`
+    /// for the prefix or for the bytes it announces.
+    fn get_u64_le_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_u64_le_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned 64 bit integer in
+    /// little-endian byte order.
+    ///
+    /// The current position is advanced by 8 plus the length.
+    ///
+    /// Returns `Err(TryGetError)` when there are not enough remaining bytes for
+    /// the prefix or for the bytes it announces, in which case the current
+    /// position is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, TryGetError};
+    ///
+    /// let mut buf = &b"\x05\x00\x00\x00\x00\x00\x00\x00hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_u64_le_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x0C\x00\x00\x00\x00\x00\x00\x00hello world"[..];
+    /// assert_eq!(Err(TryGetError { requested: 20, available: 19 }), buf.try_get_u64_le_prefixed_bytes());
+    /// assert_eq!(19, buf.remaining());
+    /// ```
+    fn try_get_u64_le_prefixed_bytes(&mut self) -> Result<crate::Bytes, TryGetError> {
+        buf_try_get_prefixed_impl!(self, u64::peek_u64_le);
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned LEB128 varint.
+    ///
+    /// The current position is advanced by the length of the prefix plus the
+    /// length it encodes. The bytes are obtained through `copy_to_bytes`, so
+    /// no copy is made when `self` is a `Bytes` or a `BytesMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut buf = Bytes::from_static(b"\x05hello world");
+    /// assert_eq!(Bytes::from_static(b"hello"), buf.get_uvarint_prefixed_bytes());
+    /// assert_eq!(6, buf.remaining());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the prefix is not a valid varint or if there is
+    /// not enough remaining data in `self` for the bytes it announces.
+    fn get_uvarint_prefixed_bytes(&mut self) -> crate::Bytes {
+        self.try_get_uvarint_prefixed_bytes()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Gets a `Bytes` prefixed by its length as an unsigned LEB128 varint.
+    ///
+    /// The current position is advanced by the length of the prefix plus the
+    /// length it encodes.
+    ///
+    /// Returns `Err(VarintError::Truncated)` when `self` ends before the end of
+    /// the prefix or of the bytes it announces, and `Err(VarintError::Overflow)`
+    /// when the prefix does not fit in a `u64`. The current position is left
+    /// unchanged on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes};
+    /// use bytes::buf::VarintError;
+    ///
+    /// let mut buf = &b"\x05hello world"[..];
+    /// assert_eq!(Ok(Bytes::from_static(b"hello")), buf.try_get_uvarint_prefixed_bytes());
+    ///
+    /// let mut buf = &b"\x0Chello world"[..];
+    /// assert_eq!(Err(VarintError::Truncated), buf.try_get_uvarint_prefixed_bytes());
+    /// assert_eq!(12, buf.remaining());
+    /// ```
+    fn try_get_uvarint_prefixed_bytes(&mut self) -> Result<crate::Bytes, VarintError> {
+        let (len, prefix_len) = varint::peek_uvarint(self, varint::MAX_LEN_U64, 0x01)?;
+
+        if len > (self.remaining() - prefix_len) as u64 {
+            return Err(VarintError::Truncated);
+        }
+
+        self.advance(prefix_len);
+        Ok(self.copy_to_bytes(len as usize))
+    }
+
+    /// Returns the offset from the current position of the first occurrence of
+    /// `byte` in the remaining bytes, or `None` if there is none.
+    ///
+    /// The current position is not advanced. The bytes are searched through
+    /// [`for_each_chunk`], and `Err(TryGetError)` is returned when `byte` was
+    /// not found in the bytes it reaches but they are not all of the remaining
+    /// ones. `available` is then the number of bytes that were searched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = (&b"hello "[..]).chain(&b"world"[..]);
+    ///
+    /// assert_eq!(Ok(Some(2)), buf.find_byte(b'l'));
+    /// assert_eq!(Ok(Some(6)), buf.find_byte(b'w'));
+    /// assert_eq!(Ok(None), buf.find_byte(b'!'));
+    /// ```
+    ///
+    /// [`for_each_chunk`]: #method.for_each_chunk
+    fn find_byte(&self, byte: u8) -> Result<Option<usize>, TryGetError> {
+        search::find_byte(self, byte)
+    }
+
+    /// Returns the offset from the current position of the first occurrence of
+    /// `needle` in the remaining bytes, or `None` if there is none.
+    ///
+    /// Occurrences that straddle several chunks are found as well. The current
+    /// position is not advanced. As with [`find_byte`], `Err(TryGetError)` is
+    /// returned when `needle` was not found in the bytes reached by
+    /// [`for_each_chunk`] but they are not all of the remaining ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let buf = (&b"GET / HTTP/1.1\r"[..]).chain(&b"\nHost: example.com\r\n"[..]);
+    ///
+    /// assert_eq!(Ok(Some(14)), buf.find_slice(b"\r\n"));
+    /// assert_eq!(Ok(None), buf.find_slice(b"\r\n\r\n"));
+    /// ```
+    ///
+    /// [`find_byte`]: #method.find_byte
+    /// [`for_each_chunk`]: #method.for_each_chunk
+    fn find_slice(&self, needle: &[u8]) -> Result<Option<usize>, TryGetError> {
+        search::find_slice(self, needle)
+    }
+
+    /// Consumes `len` bytes inside self and returns new instance of `Bytes`
+    /// with this data.
+    ///
+    /// This function may be optimized by the underlying type to avoid actual
+    /// copies. For example, `Bytes` implementation will do a shallow copy
+    /// (ref-count increment).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let bytes = (&b"hello world"[..]).copy_to_bytes(5);
+    /// assert_eq!(&bytes[..], &b"hello"[..]);
+    /// ```
+    fn copy_to_bytes(&mut self, len: usize) -> crate::Bytes {
+        use super::BufMut;
+
+        assert!(len <= self.remaining(), "`len` greater than remaining");
+
+        let mut ret = crate::BytesMut::with_capacity(len);
+        ret.put(self.take(len));
+        ret.freeze()
+    }
+
+    /// Creates an adaptor which will read at most `limit` bytes from `self`.
+    ///
+    /// This function returns a new instance of `Buf` which will read at most
+    /// `limit` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, BufMut};
+    ///
+    /// let mut buf = b"hello world"[..].take(5);
+    /// let mut dst = vec![];
+    ///
+    /// dst.put(&mut buf);
+    /// assert_eq!(dst, b"hello");
+    ///
+    /// let mut buf = buf.into_inner();
+    /// dst.clear();
+    /// dst.put(&mut buf);
+    /// assert_eq!(dst, b" world");
+    /// ```
+    fn take(self, limit: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        take::new(self, limit)
+    }
+
+    /// Creates an adaptor which will chain this buffer with another.
+    ///
+    /// The returned `Buf` instance will first consume all bytes from `self`.
+    /// Afterwards the output is equivalent to the output of next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    ///
+    /// let mut chain = b"hello "[..].chain(&b"world"[..]);
+    ///
+    /// let full = chain.copy_to_bytes(11);
+    /// assert_eq!(full.chunk(), b"hello world");
+    /// ```
+    fn chain<U: Buf>(self, next: U) -> Chain<Self, U>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+
+    /// Creates an iterator over the lines of `self`.
+    ///
+    /// Each line is yielded as `Bytes`, without its `\n` or `\r\n` line
+    /// ending, and is obtained through `copy_to_bytes` so no copy is made when
+    /// `self` is a `Bytes` or a `BytesMut`.
+    ///
+    /// The iterator returns `None` once there is no complete line left, and
+    /// leaves any trailing partial line in the buffer. More input can then be
+    /// appended through `Lines::get_mut`, after which iteration may resume, so
+    /// the iterator is not fused. The partial line is not searched again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, BytesMut};
+    ///
+    /// let mut lines = BytesMut::from(&b"HELO a\r\nMAIL FR"[..]).lines();
+    ///
+    /// assert_eq!(lines.next().unwrap(), &b"HELO a"[..]);
+    /// assert_eq!(lines.next(), None);
+    ///
+    /// lines.get_mut().extend_from_slice(b"OM:<b>\n");
+    /// assert_eq!(lines.next().unwrap(), &b"MAIL FROM:<b>"[..]);
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics if `self` does not override [`for_each_chunk`] and
+    /// its default cannot reach a line ending past the first chunks.
+    ///
+    /// [`for_each_chunk`]: #method.for_each_chunk
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        lines::new(self)
+    }
+
+    /// Creates an adaptor which implements the `Read` trait for `self`.
+    ///
+    /// This function returns a new value which implements `Read` by adapting
+    /// the `Read` trait functions to the `Buf` trait functions. Given that
+    /// `Buf` operations are infallible, none of the `Read` functions will
+    /// return with `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Bytes, Buf};
+    /// use std::io::Read;
+    ///
+    /// let buf = Bytes::from("hello world");
+    ///
+    /// let mut reader = buf.reader();
+    /// let mut dst = [0; 1024];
+    ///
+    /// let num = reader.read(&mut dst).unwrap();
+    ///
+    /// assert_eq!(11, num);
+    /// assert_eq!(&dst[..11], &b"hello world"[..]);
+    /// ```
+    #[cfg(feature = "std")]
+    fn reader(self) -> Reader<Self>
+    where
+        Self: Sized,
+    {
+        reader::new(self)
+    }
+}
+
+macro_rules! deref_forward_buf {
+    () => {
+        fn remaining(&self) -> usize {
+            (**self).remaining()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            (**self).chunk()
+        }
+
+        #[cfg(feature = "std")]
+        fn chunks_vectored<'b>(&'b self, dst: &mut [IoSlice<'b>]) -> usize {
+            (**self).chunks_vectored(dst)
+        }
+
+        fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+            (**self).for_each_chunk(f)
+        }
+
+        fn advance(&mut self, cnt: usize) {
+            (**self).advance(cnt)
         }
 
         fn has_remaining(&self) -> bool {
@@ -1004,6 +3101,166 @@ macro_rules! deref_forward_buf {
             (**self).get_int_le(nbytes)
         }
 
+        fn try_copy_to_slice(&mut self, dst: &mut [u8]) -> Result<(), TryGetError> {
+            (**self).try_copy_to_slice(dst)
+        }
+
+        fn try_get_u8(&mut self) -> Result<u8, TryGetError> {
+            (**self).try_get_u8()
+        }
+
+        fn try_get_i8(&mut self) -> Result<i8, TryGetError> {
+            (**self).try_get_i8()
+        }
+
+        fn try_get_u16(&mut self) -> Result<u16, TryGetError> {
+            (**self).try_get_u16()
+        }
+
+        fn try_get_u16_le(&mut self) -> Result<u16, TryGetError> {
+            (**self).try_get_u16_le()
+        }
+
+        fn try_get_i16(&mut self) -> Result<i16, TryGetError> {
+            (**self).try_get_i16()
+        }
+
+        fn try_get_i16_le(&mut self) -> Result<i16, TryGetError> {
+            (**self).try_get_i16_le()
+        }
+
+        fn try_get_u32(&mut self) -> Result<u32, TryGetError> {
+            (**self).try_get_u32()
+        }
+
+        fn try_get_u32_le(&mut self) -> Result<u32, TryGetError> {
+            (**self).try_get_u32_le()
+        }
+
+        fn try_get_i32(&mut self) -> Result<i32, TryGetError> {
+            (**self).try_get_i32()
+        }
+
+        fn try_get_i32_le(&mut self) -> Result<i32, TryGetError> {
+            (**self).try_get_i32_le()
+        }
+
+        fn try_get_u64(&mut self) -> Result<u64, TryGetError> {
+            (**self).try_get_u64()
+        }
+
+        fn try_get_u64_le(&mut self) -> Result<u64, TryGetError> {
+            (**self).try_get_u64_le()
+        }
+
+        fn try_get_i64(&mut self) -> Result<i64, TryGetError> {
+            (**self).try_get_i64()
+        }
+
+        fn try_get_i64_le(&mut self) -> Result<i64, TryGetError> {
+            (**self).try_get_i64_le()
+        }
+
+        fn try_get_uint(&mut self, nbytes: usize) -> Result<u64, TryGetError> {
+            (**self).try_get_uint(nbytes)
+        }
+
+        fn try_get_uint_le(&mut self, nbytes: usize) -> Result<u64, TryGetError> {
+            (**self).try_get_uint_le(nbytes)
+        }
+
+        fn try_get_int(&mut self, nbytes: usize) -> Result<i64, TryGetError> {
+            (**self).try_get_int(nbytes)
+        }
+
+        fn try_get_int_le(&mut self, nbytes: usize) -> Result<i64, TryGetError> {
+            (**self).try_get_int_le(nbytes)
+        }
+
+        fn peek_slice(&self, dst: &mut [u8]) -> Result<(), TryGetError> {
+            (**self).peek_slice(dst)
+        }
+
+        fn peek_u8(&self) -> Result<u8, TryGetError> {
+            (**self).peek_u8()
+        }
+
+        fn peek_i8(&self) -> Result<i8, TryGetError> {
+            (**self).peek_i8()
+        }
+
+        fn peek_u16(&self) -> Result<u16, TryGetError> {
+            (**self).peek_u16()
+        }
+
+        fn peek_u16_le(&self) -> Result<u16, TryGetError> {
+            (**self).peek_u16_le()
+        }
+
+        fn peek_i16(&self) -> Result<i16, TryGetError> {
+            (**self).peek_i16()
+        }
+
+        fn peek_i16_le(&self) -> Result<i16, TryGetError> {
+            (**self).peek_i16_le()
+        }
+
+        fn peek_u32(&self) -> Result<u32, TryGetError> {
+            (**self).peek_u32()
+        }
+
+        fn peek_u32_le(&self) -> Result<u32, TryGetError> {
+            (**self).peek_u32_le()
+        }
+
+        fn peek_i32(&self) -> Result<i32, TryGetError> {
+            (**self).peek_i32()
+        }
+
+        fn peek_i32_le(&self) -> Result<i32, TryGetError> {
+            (**self).peek_i32_le()
+        }
+
+        fn peek_u64(&self) -> Result<u64, TryGetError> {
+            (**self).peek_u64()
+        }
+
+        fn peek_u64_le(&self) -> Result<u64, TryGetError> {
+            (**self).peek_u64_le()
+        }
+
+        fn peek_i64(&self) -> Result<i64, TryGetError> {
+            (**self).peek_i64()
+        }
+
+        fn peek_i64_le(&self) -> Result<i64, TryGetError> {
+            (**self).peek_i64_le()
+        }
+
+        fn peek_uint(&self, nbytes: usize) -> Result<u64, TryGetError> {
+            (**self).peek_uint(nbytes)
+        }
+
+        fn peek_uint_le(&self, nbytes: usize) -> Result<u64, TryGetError> {
+            (**self).peek_uint_le(nbytes)
+        }
+
+        fn peek_int(&self, nbytes: usize) -> Result<i64, TryGetError> {
+            (**self).peek_int(nbytes)
+        }
+
+        fn peek_int_le(&self, nbytes: usize) -> Result<i64, TryGetError> {
+            (**self).peek_int_le(nbytes)
+        }
+
+        fn find_byte(&self, byte: u8) -> Result<Option<usize>, TryGetError> {
+            (**self).find_byte(byte)
+        }
+
+        fn find_slice(&self, needle: &[u8]) -> Result<Option<usize>, TryGetError> {
+            (**self).find_slice(needle)
+        }
+
         fn copy_to_bytes(&mut self, len: usize) -> crate::Bytes {
             (**self).copy_to_bytes(len)
         }
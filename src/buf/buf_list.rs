@@ -0,0 +1,208 @@
+use alloc::collections::VecDeque;
+use core::iter::FromIterator;
+
+use crate::{Buf, BufMut, Bytes, BytesMut};
+
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+/// A queue of `Bytes` segments read as one continuous buffer.
+///
+/// `BufList` is the unbounded counterpart of [`Chain`]: segments are appended
+/// with [`push_back`] as they arrive and consumed from the front through the
+/// [`Buf`] implementation. The total number of remaining bytes is kept up to
+/// date, so `remaining()` does not walk the segments.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::buf::BufList;
+/// use bytes::{Buf, Bytes};
+///
+/// let mut list = BufList::new();
+/// list.push_back(Bytes::from_static(b"hello "));
+/// list.push_back(Bytes::from_static(b"world"));
+///
+/// assert_eq!(list.remaining(), 11);
+/// assert_eq!(list.copy_to_bytes(11), &b"hello world"[..]);
+/// ```
+///
+/// [`Chain`]: struct.Chain.html
+/// [`push_back`]: #method.push_back
+/// [`Buf`]: ../trait.Buf.html
+#[derive(Clone, Debug, Default)]
+pub struct BufList {
+    // Invariant: no segment is empty.
+    bufs: VecDeque<Bytes>,
+    remaining: usize,
+}
+
+impl BufList {
+    /// Creates a new, empty `BufList`.
+    ///
+    /// This function does not allocate.
+    pub fn new() -> BufList {
+        BufList {
+            bufs: VecDeque::new(),
+            remaining: 0,
+        }
+    }
+
+    /// Creates a new, empty `BufList` with space for at least `capacity`
+    /// segments.
+    pub fn with_capacity(capacity: usize) -> BufList {
+        BufList {
+            bufs: VecDeque::with_capacity(capacity),
+            remaining: 0,
+        }
+    }
+
+    /// Appends a segment to the back of the list.
+    ///
+    /// Empty segments are dropped rather than stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::buf::BufList;
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut list = BufList::new();
+    /// list.push_back(Bytes::from_static(b"abc"));
+    /// list.push_back(Bytes::new());
+    ///
+    /// assert_eq!(list.remaining(), 3);
+    /// assert_eq!(list.num_segments(), 1);
+    /// ```
+    pub fn push_back(&mut self, bytes: Bytes) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        self.remaining = self.remaining.checked_add(bytes.len()).unwrap();
+        self.bufs.push_back(bytes);
+    }
+
+    /// Removes the first segment from the list and returns it, or `None` if
+    /// the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::buf::BufList;
+    /// use bytes::{Buf, Bytes};
+    ///
+    /// let mut list = BufList::new();
+    /// list.push_back(Bytes::from_static(b"abc"));
+    /// list.push_back(Bytes::from_static(b"def"));
+    /// list.advance(1);
+    ///
+    /// assert_eq!(list.pop_front().unwrap(), &b"bc"[..]);
+    /// assert_eq!(list.remaining(), 3);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<Bytes> {
+        let bytes = self.bufs.pop_front()?;
+        self.remaining -= bytes.len();
+        Some(bytes)
+    }
+
+    /// Returns the number of segments in the list.
+    pub fn num_segments(&self) -> usize {
+        self.bufs.len()
+    }
+}
+
+impl Buf for BufList {
+    fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn chunk(&self) -> &[u8] {
+        match self.bufs.front() {
+            Some(bytes) => bytes,
+            None => &[],
+        }
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        assert!(
+            cnt <= self.remaining,
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.remaining,
+        );
+
+        self.remaining -= cnt;
+
+        while cnt > 0 {
+            let front = &mut self.bufs[0];
+            if front.len() > cnt {
+                front.advance(cnt);
+                return;
+            }
+
+            cnt -= front.len();
+            self.bufs.pop_front();
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let mut n = 0;
+        for (bytes, slot) in self.bufs.iter().zip(dst.iter_mut()) {
+            *slot = IoSlice::new(bytes);
+            n += 1;
+        }
+        n
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        for bytes in &self.bufs {
+            if !f(bytes) {
+                return;
+            }
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        assert!(len <= self.remaining, "`len` greater than remaining");
+
+        match self.bufs.front_mut() {
+            // the request falls inside the first segment
+            Some(front) if front.len() > len => {
+                self.remaining -= len;
+                front.split_to(len)
+            }
+            Some(front) if front.len() == len => self.pop_front().unwrap(),
+            _ => {
+                let mut ret = BytesMut::with_capacity(len);
+                ret.put(self.take(len));
+                ret.freeze()
+            }
+        }
+    }
+}
+
+impl Extend<Bytes> for BufList {
+    fn extend<T: IntoIterator<Item = Bytes>>(&mut self, iter: T) {
+        for bytes in iter {
+            self.push_back(bytes);
+        }
+    }
+}
+
+impl FromIterator<Bytes> for BufList {
+    fn from_iter<T: IntoIterator<Item = Bytes>>(iter: T) -> BufList {
+        let mut list = BufList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl From<Bytes> for BufList {
+    fn from(bytes: Bytes) -> BufList {
+        let mut list = BufList::new();
+        list.push_back(bytes);
+        list
+    }
+}
@@ -1,11 +1,19 @@
-use crate::buf::{limit, Chain, Limit, UninitSlice};
+use crate::buf::{limit, varint, Chain, Limit, UninitSlice};
 #[cfg(feature = "std")]
 use crate::buf::{writer, Writer};
+use crate::TryPutError;
 
-use core::{cmp, mem, ptr, usize};
+#[cfg(feature = "std")]
+use std::io::IoSliceMut;
+
+use core::{cmp, fmt, mem, ptr, usize};
 
 use alloc::{boxed::Box, vec::Vec};
 
+/// Upper bound on the bytes the default `chunks_mut_vectored` zeroes per call.
+#[cfg(feature = "std")]
+const MAX_ZEROED_CHUNK: usize = 8 * 1024;
+
 /// A trait for values that provide sequential write access to bytes.
 ///
 /// Write bytes to a buffer
@@ -174,6 +182,79 @@ pub unsafe trait BufMut {
     #[cfg_attr(docsrs, doc(alias = "bytes_mut"))]
     fn chunk_mut(&mut self) -> &mut UninitSlice;
 
+    /// Fills `dst` with potentially multiple writable slices starting at
+    /// `self`'s current position.
+    ///
+    /// This is the `BufMut` counterpart of [`Buf::chunks_vectored`]. If the
+    /// free space of the `BufMut` is made of disjoint slices,
+    /// `chunks_mut_vectored` enables fetching more than one of them at once.
+    /// `dst` is a slice of `IoSliceMut`, so that it can be directly used with
+    /// [`readv`]. The slices are returned in the order in which they are
+    /// written to, so after writing `cnt` bytes to them, in order, calling
+    /// `advance_mut(cnt)` commits the written data.
+    ///
+    /// Returns the number of entries of `dst` that were filled. The sum of
+    /// their lengths will be less than or equal to `BufMut::remaining_mut()`.
+    ///
+    /// `IoSliceMut` may only point to initialized memory, so the default
+    /// implementation fills the slice returned by `chunk_mut` with zeroes
+    /// before returning it. To keep the cost of each call independent of the
+    /// spare capacity, at most the first 8 KiB of that slice are zeroed and
+    /// returned, even if `chunk_mut` is longer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    /// use std::io::{IoSliceMut, Read};
+    ///
+    /// let mut header = [0; 2];
+    /// let mut body = Vec::with_capacity(8);
+    /// let mut src = &b"\x00\x05hello"[..];
+    ///
+    /// {
+    ///     let mut buf = (&mut header[..]).chain_mut(&mut body);
+    ///     let mut dst = [IoSliceMut::new(&mut []), IoSliceMut::new(&mut [])];
+    ///
+    ///     assert_eq!(2, buf.chunks_mut_vectored(&mut dst));
+    ///     let n = src.read_vectored(&mut dst).unwrap();
+    ///     unsafe { buf.advance_mut(n) };
+    /// }
+    ///
+    /// assert_eq!(header, [0, 5]);
+    /// assert_eq!(body, b"hello");
+    /// ```
+    ///
+    /// # Implementer notes
+    ///
+    /// This function should never panic. Once the end of the buffer is
+    /// reached, i.e., `BufMut::remaining_mut` returns 0, calls to
+    /// `chunks_mut_vectored` must return 0 without mutating `dst`.
+    ///
+    /// Implementations should also take care to properly handle being called
+    /// with `dst` being a zero length slice.
+    ///
+    /// Implementations whose free space is already initialized should
+    /// override the default to hand it out without zeroing it.
+    ///
+    /// [`Buf::chunks_vectored`]: trait.Buf.html#method.chunks_vectored
+    /// [`readv`]: http://man7.org/linux/man-pages/man2/readv.2.html
+    #[cfg(feature = "std")]
+    fn chunks_mut_vectored<'a>(&'a mut self, dst: &mut [IoSliceMut<'a>]) -> usize {
+        if dst.is_empty() || !self.has_remaining_mut() {
+            return 0;
+        }
+
+        let chunk = self.chunk_mut();
+        let len = cmp::min(chunk.len(), MAX_ZEROED_CHUNK);
+        unsafe {
+            ptr::write_bytes(chunk.as_mut_ptr(), 0, len);
+            // the chunk was just initialized
+            dst[0] = IoSliceMut::new(core::slice::from_raw_parts_mut(chunk.as_mut_ptr(), len));
+        }
+        1
+    }
+
     /// Transfer bytes into `self` from `src` and advance the cursor by the
     /// number of bytes written.
     ///
@@ -874,111 +955,1010 @@ pub unsafe trait BufMut {
         self.put_u64_le(n.to_bits());
     }
 
-    /// Creates an adaptor which can write at most `limit` bytes to `self`.
+    /// Transfer bytes into `self` from `src` and advance the cursor by the
+    /// number of bytes written.
+    ///
+    /// Returns `Err(TryPutError)` when `self` does not have enough remaining
+    /// capacity to contain all of `src`, in which case nothing is written.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::BufMut;
+    /// use bytes::{BufMut, TryPutError};
     ///
-    /// let arr = &mut [0u8; 128][..];
-    /// assert_eq!(arr.remaining_mut(), 128);
+    /// let mut dst = [0; 6];
     ///
-    /// let dst = arr.limit(10);
-    /// assert_eq!(dst.remaining_mut(), 10);
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_slice(b"hello"));
+    ///     assert_eq!(Err(TryPutError { requested: 6, available: 1 }), buf.try_put_slice(b" world"));
+    ///
+    ///     assert_eq!(1, buf.remaining_mut());
+    /// }
+    ///
+    /// assert_eq!(b"hello\0", &dst);
     /// ```
-    fn limit(self, limit: usize) -> Limit<Self>
-    where
-        Self: Sized,
-    {
-        limit::new(self, limit)
+    fn try_put_slice(&mut self, src: &[u8]) -> Result<(), TryPutError> {
+        if self.remaining_mut() < src.len() {
+            return Err(TryPutError {
+                requested: src.len(),
+                available: self.remaining_mut(),
+            });
+        }
+
+        self.put_slice(src);
+        Ok(())
     }
 
-    /// Creates an adaptor which implements the `Write` trait for `self`.
+    /// Put `cnt` bytes `val` into `self`.
     ///
-    /// This function returns a new value which implements `Write` by adapting
-    /// the `Write` trait functions to the `BufMut` trait functions. Given that
-    /// `BufMut` operations are infallible, none of the `Write` functions will
-    /// return with `Err`.
+    /// Returns `Err(TryPutError)` when `self` has less than `cnt` remaining
+    /// capacity, in which case nothing is written.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::BufMut;
-    /// use std::io::Write;
+    /// use bytes::{BufMut, TryPutError};
     ///
-    /// let mut buf = vec![].writer();
+    /// let mut dst = [0; 6];
     ///
-    /// let num = buf.write(&b"hello world"[..]).unwrap();
-    /// assert_eq!(11, num);
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_bytes(b'a', 4));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 2 }), buf.try_put_bytes(b'b', 4));
+    /// }
     ///
-    /// let buf = buf.into_inner();
+    /// assert_eq!(b"aaaa\0\0", &dst);
+    /// ```
+    fn try_put_bytes(&mut self, val: u8, cnt: usize) -> Result<(), TryPutError> {
+        if self.remaining_mut() < cnt {
+            return Err(TryPutError {
+                requested: cnt,
+                available: self.remaining_mut(),
+            });
+        }
+
+        self.put_bytes(val, cnt);
+        Ok(())
+    }
+
+    /// Writes an unsigned 8 bit integer to `self`.
+    ///
+    /// The current position is advanced by 1.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(*buf, b"hello world"[..]);
     /// ```
-    #[cfg(feature = "std")]
-    fn writer(self) -> Writer<Self>
-    where
-        Self: Sized,
-    {
-        writer::new(self)
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 1];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u8(0x01));
+    ///     assert_eq!(Err(TryPutError { requested: 1, available: 0 }), buf.try_put_u8(0x01));
+    /// }
+    ///
+    /// assert_eq!(b"\x01", &dst);
+    /// ```
+    fn try_put_u8(&mut self, n: u8) -> Result<(), TryPutError> {
+        self.try_put_slice(&[n])
     }
 
-    /// Creates an adapter which will chain this buffer with another.
+    /// Writes a signed 8 bit integer to `self`.
     ///
-    /// The returned `BufMut` instance will first write to all bytes from
-    /// `self`. Afterwards, it will write to `next`.
+    /// The current position is advanced by 1.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
     ///
     /// # Examples
     ///
     /// ```
-    /// use bytes::BufMut;
+    /// use bytes::{BufMut, TryPutError};
     ///
-    /// let mut a = [0u8; 5];
-    /// let mut b = [0u8; 6];
+    /// let mut dst = [0; 1];
     ///
-    /// let mut chain = (&mut a[..]).chain_mut(&mut b[..]);
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i8(0x01));
+    ///     assert_eq!(Err(TryPutError { requested: 1, available: 0 }), buf.try_put_i8(0x01));
+    /// }
     ///
-    /// chain.put_slice(b"hello world");
+    /// assert_eq!(b"\x01", &dst);
+    /// ```
+    fn try_put_i8(&mut self, n: i8) -> Result<(), TryPutError> {
+        self.try_put_slice(&[n as u8])
+    }
+
+    /// Writes an unsigned 16 bit integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(&a[..], b"hello");
-    /// assert_eq!(&b[..], b" world");
     /// ```
-    fn chain_mut<U: BufMut>(self, next: U) -> Chain<Self, U>
-    where
-        Self: Sized,
-    {
-        Chain::new(self, next)
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 3];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u16(0x0809));
+    ///     assert_eq!(Err(TryPutError { requested: 2, available: 1 }), buf.try_put_u16(0x0809));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x09\0", &dst);
+    /// ```
+    fn try_put_u16(&mut self, n: u16) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
     }
-}
 
-macro_rules! deref_forward_bufmut {
-    () => {
-        fn remaining_mut(&self) -> usize {
-            (**self).remaining_mut()
-        }
+    /// Writes an unsigned 16 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 3];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u16_le(0x0809));
+    ///     assert_eq!(Err(TryPutError { requested: 2, available: 1 }), buf.try_put_u16_le(0x0809));
+    /// }
+    ///
+    /// assert_eq!(b"\x09\x08\0", &dst);
+    /// ```
+    fn try_put_u16_le(&mut self, n: u16) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
 
-        fn chunk_mut(&mut self) -> &mut UninitSlice {
-            (**self).chunk_mut()
-        }
+    /// Writes a signed 16 bit integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 3];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i16(0x0809));
+    ///     assert_eq!(Err(TryPutError { requested: 2, available: 1 }), buf.try_put_i16(0x0809));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x09\0", &dst);
+    /// ```
+    fn try_put_i16(&mut self, n: i16) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
 
-        unsafe fn advance_mut(&mut self, cnt: usize) {
-            (**self).advance_mut(cnt)
-        }
+    /// Writes a signed 16 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 2.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 3];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i16_le(0x0809));
+    ///     assert_eq!(Err(TryPutError { requested: 2, available: 1 }), buf.try_put_i16_le(0x0809));
+    /// }
+    ///
+    /// assert_eq!(b"\x09\x08\0", &dst);
+    /// ```
+    fn try_put_i16_le(&mut self, n: i16) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
 
-        fn put_slice(&mut self, src: &[u8]) {
-            (**self).put_slice(src)
-        }
+    /// Writes an unsigned 32 bit integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u32(0x0809A0A1));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_u32(0x0809A0A1));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x09\xA0\xA1\0", &dst);
+    /// ```
+    fn try_put_u32(&mut self, n: u32) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
 
-        fn put_u8(&mut self, n: u8) {
-            (**self).put_u8(n)
-        }
+    /// Writes an unsigned 32 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u32_le(0x0809A0A1));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_u32_le(0x0809A0A1));
+    /// }
+    ///
+    /// assert_eq!(b"\xA1\xA0\x09\x08\0", &dst);
+    /// ```
+    fn try_put_u32_le(&mut self, n: u32) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
 
-        fn put_i8(&mut self, n: i8) {
-            (**self).put_i8(n)
-        }
+    /// Writes a signed 32 bit integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i32(0x0809A0A1));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_i32(0x0809A0A1));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x09\xA0\xA1\0", &dst);
+    /// ```
+    fn try_put_i32(&mut self, n: i32) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
 
-        fn put_u16(&mut self, n: u16) {
+    /// Writes a signed 32 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i32_le(0x0809A0A1));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_i32_le(0x0809A0A1));
+    /// }
+    ///
+    /// assert_eq!(b"\xA1\xA0\x09\x08\0", &dst);
+    /// ```
+    fn try_put_i32_le(&mut self, n: i32) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
+
+    /// Writes an unsigned 64 bit integer to `self` in the big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u64(0x0102030405060708));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_u64(0x0102030405060708));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\x04\x05\x06\x07\x08\0", &dst);
+    /// ```
+    fn try_put_u64(&mut self, n: u64) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
+
+    /// Writes an unsigned 64 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u64_le(0x0102030405060708));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_u64_le(0x0102030405060708));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x07\x06\x05\x04\x03\x02\x01\0", &dst);
+    /// ```
+    fn try_put_u64_le(&mut self, n: u64) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
+
+    /// Writes a signed 64 bit integer to `self` in the big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i64(0x0102030405060708));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_i64(0x0102030405060708));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\x04\x05\x06\x07\x08\0", &dst);
+    /// ```
+    fn try_put_i64(&mut self, n: i64) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
+
+    /// Writes a signed 64 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i64_le(0x0102030405060708));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_i64_le(0x0102030405060708));
+    /// }
+    ///
+    /// assert_eq!(b"\x08\x07\x06\x05\x04\x03\x02\x01\0", &dst);
+    /// ```
+    fn try_put_i64_le(&mut self, n: i64) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
+
+    /// Writes an unsigned 128 bit integer to `self` in the big-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 17];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u128(0x01020304050607080910111213141516));
+    ///     assert_eq!(Err(TryPutError { requested: 16, available: 1 }), buf.try_put_u128(0x01020304050607080910111213141516));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16\0", &dst);
+    /// ```
+    fn try_put_u128(&mut self, n: u128) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
+
+    /// Writes an unsigned 128 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 17];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_u128_le(0x01020304050607080910111213141516));
+    ///     assert_eq!(Err(TryPutError { requested: 16, available: 1 }), buf.try_put_u128_le(0x01020304050607080910111213141516));
+    /// }
+    ///
+    /// assert_eq!(b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01\0", &dst);
+    /// ```
+    fn try_put_u128_le(&mut self, n: u128) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
+
+    /// Writes a signed 128 bit integer to `self` in the big-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 17];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i128(0x01020304050607080910111213141516));
+    ///     assert_eq!(Err(TryPutError { requested: 16, available: 1 }), buf.try_put_i128(0x01020304050607080910111213141516));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x10\x11\x12\x13\x14\x15\x16\0", &dst);
+    /// ```
+    fn try_put_i128(&mut self, n: i128) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes())
+    }
+
+    /// Writes a signed 128 bit integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 16.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 17];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_i128_le(0x01020304050607080910111213141516));
+    ///     assert_eq!(Err(TryPutError { requested: 16, available: 1 }), buf.try_put_i128_le(0x01020304050607080910111213141516));
+    /// }
+    ///
+    /// assert_eq!(b"\x16\x15\x14\x13\x12\x11\x10\x09\x08\x07\x06\x05\x04\x03\x02\x01\0", &dst);
+    /// ```
+    fn try_put_i128_le(&mut self, n: i128) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes())
+    }
+
+    /// Writes an unsigned n-byte integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 4];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_uint(0x010203, 3));
+    ///     assert_eq!(Err(TryPutError { requested: 3, available: 1 }), buf.try_put_uint(0x010203, 3));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\0", &dst);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` is greater than 8.
+    fn try_put_uint(&mut self, n: u64, nbytes: usize) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes()[mem::size_of_val(&n) - nbytes..])
+    }
+
+    /// Writes an unsigned n-byte integer to `self` in the little-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 4];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_uint_le(0x010203, 3));
+    ///     assert_eq!(Err(TryPutError { requested: 3, available: 1 }), buf.try_put_uint_le(0x010203, 3));
+    /// }
+    ///
+    /// assert_eq!(b"\x03\x02\x01\0", &dst);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` is greater than 8.
+    fn try_put_uint_le(&mut self, n: u64, nbytes: usize) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes()[0..nbytes])
+    }
+
+    /// Writes low `nbytes` of a signed integer to `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 4];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_int(0x0504010203, 3));
+    ///     assert_eq!(Err(TryPutError { requested: 3, available: 1 }), buf.try_put_int(0x0504010203, 3));
+    /// }
+    ///
+    /// assert_eq!(b"\x01\x02\x03\0", &dst);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` is greater than 8.
+    fn try_put_int(&mut self, n: i64, nbytes: usize) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_be_bytes()[mem::size_of_val(&n) - nbytes..])
+    }
+
+    /// Writes low `nbytes` of a signed integer to `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by `nbytes`.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 4];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_int_le(0x0504010203, 3));
+    ///     assert_eq!(Err(TryPutError { requested: 3, available: 1 }), buf.try_put_int_le(0x0504010203, 3));
+    /// }
+    ///
+    /// assert_eq!(b"\x03\x02\x01\0", &dst);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `nbytes` is greater than 8.
+    fn try_put_int_le(&mut self, n: i64, nbytes: usize) -> Result<(), TryPutError> {
+        self.try_put_slice(&n.to_le_bytes()[0..nbytes])
+    }
+
+    /// Writes an IEEE754 single-precision (4 bytes) floating point number to
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_f32(1.2f32));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_f32(1.2f32));
+    /// }
+    ///
+    /// assert_eq!(b"\x3F\x99\x99\x9A\0", &dst);
+    /// ```
+    fn try_put_f32(&mut self, n: f32) -> Result<(), TryPutError> {
+        self.try_put_u32(n.to_bits())
+    }
+
+    /// Writes an IEEE754 single-precision (4 bytes) floating point number to
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 4.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 5];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_f32_le(1.2f32));
+    ///     assert_eq!(Err(TryPutError { requested: 4, available: 1 }), buf.try_put_f32_le(1.2f32));
+    /// }
+    ///
+    /// assert_eq!(b"\x9A\x99\x99\x3F\0", &dst);
+    /// ```
+    fn try_put_f32_le(&mut self, n: f32) -> Result<(), TryPutError> {
+        self.try_put_u32_le(n.to_bits())
+    }
+
+    /// Writes an IEEE754 double-precision (8 bytes) floating point number to
+    /// `self` in big-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_f64(1.2f64));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_f64(1.2f64));
+    /// }
+    ///
+    /// assert_eq!(b"\x3F\xF3\x33\x33\x33\x33\x33\x33\0", &dst);
+    /// ```
+    fn try_put_f64(&mut self, n: f64) -> Result<(), TryPutError> {
+        self.try_put_u64(n.to_bits())
+    }
+
+    /// Writes an IEEE754 double-precision (8 bytes) floating point number to
+    /// `self` in little-endian byte order.
+    ///
+    /// The current position is advanced by 8.
+    ///
+    /// Returns `Err(TryPutError)` when there is not enough remaining capacity
+    /// in `self`, in which case nothing is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, TryPutError};
+    ///
+    /// let mut dst = [0; 9];
+    ///
+    /// {
+    ///     let mut buf = &mut dst[..];
+    ///     assert_eq!(Ok(()), buf.try_put_f64_le(1.2f64));
+    ///     assert_eq!(Err(TryPutError { requested: 8, available: 1 }), buf.try_put_f64_le(1.2f64));
+    /// }
+    ///
+    /// assert_eq!(b"\x33\x33\x33\x33\x33\x33\xF3\x3F\0", &dst);
+    /// ```
+    fn try_put_f64_le(&mut self, n: f64) -> Result<(), TryPutError> {
+        self.try_put_u64_le(n.to_bits())
+    }
+
+    /// Writes an unsigned integer to `self` as a LEB128 varint, as used by
+    /// protobuf and WebAssembly.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is between 1 and 10 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut buf = vec![];
+    /// buf.put_uvarint(300);
+    /// assert_eq!(buf, b"\xAC\x02");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining capacity in
+    /// `self`.
+    fn put_uvarint(&mut self, n: u64) {
+        let mut buf = [0; varint::MAX_LEN_U64];
+        let len = varint::encode(n, &mut buf);
+        self.put_slice(&buf[..len]);
+    }
+
+    /// Writes an unsigned 32 bit integer to `self` as a LEB128 varint.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is between 1 and 5 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut buf = vec![];
+    /// buf.put_uvarint_u32(std::u32::MAX);
+    /// assert_eq!(buf, b"\xFF\xFF\xFF\xFF\x0F");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining capacity in
+    /// `self`.
+    fn put_uvarint_u32(&mut self, n: u32) {
+        self.put_uvarint(u64::from(n));
+    }
+
+    /// Writes a signed integer to `self` as a zigzag encoded LEB128 varint,
+    /// as used by the protobuf `sint64` type.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is between 1 and 10 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut buf = vec![];
+    /// buf.put_ivarint(-2);
+    /// buf.put_ivarint(2);
+    /// assert_eq!(buf, b"\x03\x04");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining capacity in
+    /// `self`.
+    fn put_ivarint(&mut self, n: i64) {
+        self.put_uvarint(varint::zigzag_encode(n));
+    }
+
+    /// Writes a signed 32 bit integer to `self` as a zigzag encoded LEB128
+    /// varint, as used by the protobuf `sint32` type.
+    ///
+    /// The current position is advanced by the length of the encoding, which
+    /// is between 1 and 5 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut buf = vec![];
+    /// buf.put_ivarint_i32(std::i32::MIN);
+    /// assert_eq!(buf, b"\xFF\xFF\xFF\xFF\x0F");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is not enough remaining capacity in
+    /// `self`.
+    fn put_ivarint_i32(&mut self, n: i32) {
+        self.put_ivarint(i64::from(n));
+    }
+
+    /// Writes formatted text to `self`, as produced by `format_args!`.
+    ///
+    /// This works the same way as `write!` with a `core::fmt::Write`, so it
+    /// is available without `std`. The current position is advanced by the
+    /// length of the formatted text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut buf = vec![];
+    /// buf.put_fmt(format_args!("{}:{}", "localhost", 8080)).unwrap();
+    /// assert_eq!(buf, b"localhost:8080");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not enough remaining capacity in `self`
+    /// for the formatted text, or if a formatting trait implementation
+    /// returns an error. Text formatted before the error is left in `self`.
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut dst = [0; 4];
+    /// let mut buf = &mut dst[..];
+    /// assert!(buf.put_fmt(format_args!("{}", 123456)).is_err());
+    /// ```
+    fn put_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        fmt::write(&mut FmtWriter(self), args)
+    }
+
+    /// Creates an adaptor which can write at most `limit` bytes to `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let arr = &mut [0u8; 128][..];
+    /// assert_eq!(arr.remaining_mut(), 128);
+    ///
+    /// let dst = arr.limit(10);
+    /// assert_eq!(dst.remaining_mut(), 10);
+    /// ```
+    fn limit(self, limit: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        limit::new(self, limit)
+    }
+
+    /// Creates an adaptor which implements the `Write` trait for `self`.
+    ///
+    /// This function returns a new value which implements `Write` by adapting
+    /// the `Write` trait functions to the `BufMut` trait functions. Given that
+    /// `BufMut` operations are infallible, none of the `Write` functions will
+    /// return with `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    /// use std::io::Write;
+    ///
+    /// let mut buf = vec![].writer();
+    ///
+    /// let num = buf.write(&b"hello world"[..]).unwrap();
+    /// assert_eq!(11, num);
+    ///
+    /// let buf = buf.into_inner();
+    ///
+    /// assert_eq!(*buf, b"hello world"[..]);
+    /// ```
+    #[cfg(feature = "std")]
+    fn writer(self) -> Writer<Self>
+    where
+        Self: Sized,
+    {
+        writer::new(self)
+    }
+
+    /// Creates an adapter which will chain this buffer with another.
+    ///
+    /// The returned `BufMut` instance will first write to all bytes from
+    /// `self`. Afterwards, it will write to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    ///
+    /// let mut a = [0u8; 5];
+    /// let mut b = [0u8; 6];
+    ///
+    /// let mut chain = (&mut a[..]).chain_mut(&mut b[..]);
+    ///
+    /// chain.put_slice(b"hello world");
+    ///
+    /// assert_eq!(&a[..], b"hello");
+    /// assert_eq!(&b[..], b" world");
+    /// ```
+    fn chain_mut<U: BufMut>(self, next: U) -> Chain<Self, U>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+}
+
+macro_rules! deref_forward_bufmut {
+    () => {
+        fn remaining_mut(&self) -> usize {
+            (**self).remaining_mut()
+        }
+
+        fn chunk_mut(&mut self) -> &mut UninitSlice {
+            (**self).chunk_mut()
+        }
+
+        unsafe fn advance_mut(&mut self, cnt: usize) {
+            (**self).advance_mut(cnt)
+        }
+
+        #[cfg(feature = "std")]
+        fn chunks_mut_vectored<'b>(&'b mut self, dst: &mut [IoSliceMut<'b>]) -> usize {
+            (**self).chunks_mut_vectored(dst)
+        }
+
+        fn put_slice(&mut self, src: &[u8]) {
+            (**self).put_slice(src)
+        }
+
+        fn put_u8(&mut self, n: u8) {
+            (**self).put_u8(n)
+        }
+
+        fn put_i8(&mut self, n: i8) {
+            (**self).put_i8(n)
+        }
+
+        fn put_u16(&mut self, n: u16) {
             (**self).put_u16(n)
         }
 
@@ -1025,6 +2005,70 @@ macro_rules! deref_forward_bufmut {
         fn put_i64_le(&mut self, n: i64) {
             (**self).put_i64_le(n)
         }
+
+        fn put_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+            (**self).put_fmt(args)
+        }
+
+        fn try_put_slice(&mut self, src: &[u8]) -> Result<(), TryPutError> {
+            (**self).try_put_slice(src)
+        }
+
+        fn try_put_u8(&mut self, n: u8) -> Result<(), TryPutError> {
+            (**self).try_put_u8(n)
+        }
+
+        fn try_put_i8(&mut self, n: i8) -> Result<(), TryPutError> {
+            (**self).try_put_i8(n)
+        }
+
+        fn try_put_u16(&mut self, n: u16) -> Result<(), TryPutError> {
+            (**self).try_put_u16(n)
+        }
+
+        fn try_put_u16_le(&mut self, n: u16) -> Result<(), TryPutError> {
+            (**self).try_put_u16_le(n)
+        }
+
+        fn try_put_i16(&mut self, n: i16) -> Result<(), TryPutError> {
+            (**self).try_put_i16(n)
+        }
+
+        fn try_put_i16_le(&mut self, n: i16) -> Result<(), TryPutError> {
+            (**self).try_put_i16_le(n)
+        }
+
+        fn try_put_u32(&mut self, n: u32) -> Result<(), TryPutError> {
+            (**self).try_put_u32(n)
+        }
+
+        fn try_put_u32_le(&mut self, n: u32) -> Result<(), TryPutError> {
+            (**self).try_put_u32_le(n)
+        }
+
+        fn try_put_i32(&mut self, n: i32) -> Result<(), TryPutError> {
+            (**self).try_put_i32(n)
+        }
+
+        fn try_put_i32_le(&mut self, n: i32) -> Result<(), TryPutError> {
+            (**self).try_put_i32_le(n)
+        }
+
+        fn try_put_u64(&mut self, n: u64) -> Result<(), TryPutError> {
+            (**self).try_put_u64(n)
+        }
+
+        fn try_put_u64_le(&mut self, n: u64) -> Result<(), TryPutError> {
+            (**self).try_put_u64_le(n)
+        }
+
+        fn try_put_i64(&mut self, n: i64) -> Result<(), TryPutError> {
+            (**self).try_put_i64(n)
+        }
+
+        fn try_put_i64_le(&mut self, n: i64) -> Result<(), TryPutError> {
+            (**self).try_put_i64_le(n)
+        }
     };
 }
 
@@ -1048,6 +2092,17 @@ unsafe impl BufMut for &mut [u8] {
         unsafe { &mut *(*self as *mut [u8] as *mut _) }
     }
 
+    #[cfg(feature = "std")]
+    fn chunks_mut_vectored<'a>(&'a mut self, dst: &mut [IoSliceMut<'a>]) -> usize {
+        if dst.is_empty() || self.is_empty() {
+            return 0;
+        }
+
+        // already initialized, no need to zero it
+        dst[0] = IoSliceMut::new(&mut self[..]);
+        1
+    }
+
     #[inline]
     unsafe fn advance_mut(&mut self, cnt: usize) {
         // Lifetime dance taken from `impl Write for &mut [u8]`.
@@ -1141,6 +2196,16 @@ unsafe impl BufMut for Vec<u8> {
     }
 }
 
+/// Adapts a `BufMut` to `fmt::Write` for `BufMut::put_fmt`, failing instead
+/// of panicking when the text doesn't fit.
+struct FmtWriter<'a, B: ?Sized>(&'a mut B);
+
+impl<B: BufMut + ?Sized> fmt::Write for FmtWriter<'_, B> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.try_put_slice(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
 // The existence of this function makes the compiler catch if the BufMut
 // trait is "object-safe" or not.
 fn _assert_trait_object(_b: &dyn BufMut) {}
@@ -2,7 +2,7 @@ use crate::buf::{IntoIter, UninitSlice};
 use crate::{Buf, BufMut, Bytes};
 
 #[cfg(feature = "std")]
-use std::io::IoSlice;
+use std::io::{IoSlice, IoSliceMut};
 
 /// A `Chain` sequences two buffers.
 ///
@@ -171,6 +171,18 @@ where
         n
     }
 
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        let mut done = false;
+        self.a.for_each_chunk(&mut |chunk| {
+            done = !f(chunk);
+            !done
+        });
+
+        if !done {
+            self.b.for_each_chunk(f);
+        }
+    }
+
     fn copy_to_bytes(&mut self, len: usize) -> Bytes {
         let a_rem = self.a.remaining();
         if a_rem >= len {
@@ -226,6 +238,20 @@ where
 
         self.b.advance_mut(cnt);
     }
+
+    #[cfg(feature = "std")]
+    fn chunks_mut_vectored<'a>(&'a mut self, dst: &mut [IoSliceMut<'a>]) -> usize {
+        let a_rem = self.a.remaining_mut();
+        let mut n = self.a.chunks_mut_vectored(dst);
+
+        // `advance_mut` only moves on to `b` once `a` is full, so `b` may
+        // only be written to if the slices cover all of `a`.
+        let a_len: usize = dst[..n].iter().map(|s| s.len()).sum();
+        if a_len == a_rem {
+            n += self.b.chunks_mut_vectored(&mut dst[n..]);
+        }
+        n
+    }
 }
 
 impl<T, U> IntoIterator for Chain<T, U>
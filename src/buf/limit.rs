@@ -3,6 +3,9 @@ use crate::BufMut;
 
 use core::cmp;
 
+#[cfg(feature = "std")]
+use std::io::IoSliceMut;
+
 /// A `BufMut` adapter which limits the amount of bytes that can be written
 /// to an underlying buffer.
 #[derive(Debug)]
@@ -72,4 +75,29 @@ unsafe impl<T: BufMut> BufMut for Limit<T> {
         self.inner.advance_mut(cnt);
         self.limit -= cnt;
     }
+
+    #[cfg(feature = "std")]
+    fn chunks_mut_vectored<'a>(&'a mut self, dst: &mut [IoSliceMut<'a>]) -> usize {
+        if self.limit == 0 {
+            return 0;
+        }
+
+        let n = self.inner.chunks_mut_vectored(dst);
+
+        let mut rem = self.limit;
+        for (i, slot) in dst[..n].iter_mut().enumerate() {
+            if rem == 0 {
+                return i;
+            }
+
+            if slot.len() > rem {
+                // Shrink the slice to the limit. It was borrowed for `'a`
+                // and the old `IoSliceMut` is overwritten.
+                let ptr = slot.as_mut_ptr();
+                *slot = IoSliceMut::new(unsafe { core::slice::from_raw_parts_mut(ptr, rem) });
+            }
+            rem -= slot.len();
+        }
+        n
+    }
 }
@@ -0,0 +1,111 @@
+use super::search;
+use crate::{Buf, Bytes};
+
+/// An iterator over the lines of a buffer, as `Bytes`.
+///
+/// This struct is generally created by calling `lines()` on `Buf`. See
+/// documentation of [`lines()`](trait.Buf.html#method.lines) for more details.
+///
+/// The iterator is not fused: after returning `None`, it returns the next
+/// line once more input was appended through [`get_mut`].
+///
+/// [`get_mut`]: #method.get_mut
+#[derive(Debug)]
+pub struct Lines<B> {
+    inner: B,
+    // The number of bytes at the front of `inner` known not to contain a
+    // `\n`, so that a partial line is not searched again on every call.
+    scanned: usize,
+}
+
+pub fn new<B>(inner: B) -> Lines<B> {
+    Lines { inner, scanned: 0 }
+}
+
+impl<B> Lines<B> {
+    /// Consumes this `Lines`, returning the underlying value.
+    ///
+    /// Any incomplete last line is left in the returned buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::Buf;
+    ///
+    /// let mut lines = (&b"hello\nwor"[..]).lines();
+    ///
+    /// assert_eq!(lines.next().unwrap(), &b"hello"[..]);
+    /// assert_eq!(lines.next(), None);
+    ///
+    /// assert_eq!(lines.into_inner(), &b"wor"[..]);
+    /// ```
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying `Buf`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::Buf;
+    ///
+    /// let lines = (&b"hello\nworld"[..]).lines();
+    ///
+    /// assert_eq!(11, lines.get_ref().remaining());
+    /// ```
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying `Buf`.
+    ///
+    /// This can be used to append more input once all complete lines have
+    /// been read. Bytes must only be appended, as the bytes already searched
+    /// for a line ending are not searched again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::{Buf, BytesMut};
+    ///
+    /// let mut lines = BytesMut::from(&b"hel"[..]).lines();
+    /// assert_eq!(lines.next(), None);
+    ///
+    /// lines.get_mut().extend_from_slice(b"lo\r\n");
+    /// assert_eq!(lines.next().unwrap(), &b"hello"[..]);
+    /// ```
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+}
+
+impl<B: Buf> Iterator for Lines<B> {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        let len = match search::find_byte_from(&self.inner, b'\n', self.scanned) {
+            Ok(len) => len,
+            Err(scanned) => {
+                assert!(
+                    scanned >= self.inner.remaining(),
+                    "`for_each_chunk` does not reach all remaining bytes: {:?} < {:?}",
+                    scanned,
+                    self.inner.remaining(),
+                );
+                self.scanned = scanned;
+                return None;
+            }
+        };
+        self.scanned = 0;
+
+        let mut line = self.inner.copy_to_bytes(len);
+        self.inner.advance(1);
+
+        if line.ends_with(b"\r") {
+            line.truncate(len - 1);
+        }
+
+        Some(line)
+    }
+}
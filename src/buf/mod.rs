@@ -17,25 +17,34 @@
 //! [`BufMut`]: trait.BufMut.html
 
 mod buf_impl;
+mod buf_list;
 mod buf_mut;
 mod chain;
 mod iter;
 mod limit;
+mod lines;
 #[cfg(feature = "std")]
 mod reader;
+mod ring_buf;
+pub(crate) mod search;
 mod take;
 mod uninit_slice;
+mod varint;
 mod vec_deque;
 #[cfg(feature = "std")]
 mod writer;
 
 pub use self::buf_impl::Buf;
+pub use self::buf_list::BufList;
 pub use self::buf_mut::BufMut;
 pub use self::chain::Chain;
 pub use self::iter::IntoIter;
 pub use self::limit::Limit;
+pub use self::lines::Lines;
+pub use self::ring_buf::RingBuf;
 pub use self::take::Take;
 pub use self::uninit_slice::UninitSlice;
+pub use self::varint::VarintError;
 
 #[cfg(feature = "std")]
 pub use self::{reader::Reader, writer::Writer};
@@ -0,0 +1,242 @@
+use alloc::boxed::Box;
+use alloc::vec;
+
+use crate::buf::UninitSlice;
+use crate::{Buf, BufMut};
+
+#[cfg(feature = "std")]
+use std::io::{IoSlice, IoSliceMut};
+
+/// A fixed-capacity circular buffer of bytes.
+///
+/// `RingBuf` is written to through [`BufMut`] and read from through [`Buf`].
+/// Its storage is allocated once, by [`with_capacity`], and is never grown
+/// or reallocated: writes wrap around to the space freed by reads, and
+/// `remaining_mut()` is the free space left.
+///
+/// Both the data and the free space can be split in two by the end of the
+/// storage. `chunk()` and `chunk_mut()` return the first part, and the second
+/// one becomes available once the first has been consumed.
+/// `chunks_vectored` and `chunks_mut_vectored` return both parts at once.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::buf::RingBuf;
+/// use bytes::{Buf, BufMut};
+///
+/// let mut ring = RingBuf::with_capacity(8);
+/// ring.put_slice(b"abcdef");
+/// ring.advance(4);
+///
+/// // wraps around the end of the storage
+/// ring.put_slice(b"ghij");
+/// assert_eq!(ring.remaining(), 6);
+/// assert_eq!(ring.remaining_mut(), 2);
+///
+/// assert_eq!(ring.copy_to_bytes(6), &b"efghij"[..]);
+/// ```
+///
+/// [`Buf`]: ../trait.Buf.html
+/// [`BufMut`]: ../trait.BufMut.html
+/// [`with_capacity`]: #method.with_capacity
+#[derive(Debug, Clone)]
+pub struct RingBuf {
+    buf: Box<[u8]>,
+    // Index of the first byte of data.
+    head: usize,
+    len: usize,
+}
+
+impl RingBuf {
+    /// Creates a new `RingBuf` which can hold `capacity` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::buf::RingBuf;
+    /// use bytes::BufMut;
+    ///
+    /// let ring = RingBuf::with_capacity(64);
+    ///
+    /// assert_eq!(ring.capacity(), 64);
+    /// assert_eq!(ring.remaining_mut(), 64);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> RingBuf {
+        RingBuf {
+            buf: vec![0; capacity].into_boxed_slice(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes the `RingBuf` can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the number of bytes contained in this `RingBuf`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the `RingBuf` contains no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the `RingBuf` has no free space left.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Removes all data from the `RingBuf`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Returns the data in the `RingBuf` as a pair of slices, which contain,
+    /// in order, the contents of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::buf::RingBuf;
+    /// use bytes::{Buf, BufMut};
+    ///
+    /// let mut ring = RingBuf::with_capacity(4);
+    /// ring.put_slice(b"abc");
+    /// ring.advance(2);
+    /// ring.put_slice(b"de");
+    ///
+    /// assert_eq!(ring.as_slices(), (&b"cd"[..], &b"e"[..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let end = self.head + self.len;
+        if end <= self.capacity() {
+            (&self.buf[self.head..end], &[])
+        } else {
+            (&self.buf[self.head..], &self.buf[..end - self.capacity()])
+        }
+    }
+
+    /// Returns the index one past the last byte of data.
+    fn tail(&self) -> usize {
+        let tail = self.head + self.len;
+        if tail >= self.capacity() {
+            tail - self.capacity()
+        } else {
+            tail
+        }
+    }
+}
+
+impl Buf for RingBuf {
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len,
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.len,
+        );
+
+        self.len -= cnt;
+        if self.len == 0 {
+            // start over at the beginning to keep the free space contiguous
+            self.head = 0;
+        } else {
+            self.head += cnt;
+            if self.head >= self.capacity() {
+                self.head -= self.capacity();
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let (s1, s2) = self.as_slices();
+
+        let mut n = 0;
+        for (s, slot) in [s1, s2].iter().zip(dst.iter_mut()) {
+            if s.is_empty() {
+                break;
+            }
+            *slot = IoSlice::new(s);
+            n += 1;
+        }
+        n
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        let (s1, s2) = self.as_slices();
+        if f(s1) && !s2.is_empty() {
+            f(s2);
+        }
+    }
+}
+
+unsafe impl BufMut for RingBuf {
+    fn remaining_mut(&self) -> usize {
+        self.capacity() - self.len
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let tail = self.tail();
+        let end = if tail < self.head || self.is_full() {
+            self.head
+        } else {
+            self.capacity()
+        };
+
+        let free = &mut self.buf[tail..end];
+        // the storage is always initialized
+        unsafe { UninitSlice::from_raw_parts_mut(free.as_mut_ptr(), free.len()) }
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining_mut(),
+            "cannot advance past `remaining_mut`: {:?} <= {:?}",
+            cnt,
+            self.remaining_mut(),
+        );
+
+        self.len += cnt;
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_mut_vectored<'a>(&'a mut self, dst: &mut [IoSliceMut<'a>]) -> usize {
+        let tail = self.tail();
+        let (s1, s2) = if tail < self.head || self.is_full() {
+            (&mut self.buf[tail..self.head], &mut [][..])
+        } else {
+            // the free space wraps around the end of the storage
+            let (front, back) = self.buf.split_at_mut(tail);
+            (back, &mut front[..self.head])
+        };
+
+        let mut n = 0;
+        for (s, slot) in [s1, s2].iter_mut().zip(dst) {
+            if s.is_empty() {
+                break;
+            }
+            *slot = IoSliceMut::new(core::mem::replace(s, &mut []));
+            n += 1;
+        }
+        n
+    }
+}
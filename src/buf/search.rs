@@ -0,0 +1,172 @@
+use core::{cmp, mem, usize};
+
+use alloc::vec::Vec;
+
+use super::Buf;
+use crate::TryGetError;
+
+const LO: usize = usize::MAX / 255;
+const HI: usize = LO * 128;
+const WORD: usize = mem::size_of::<usize>();
+
+/// Returns `true` if any byte of `x` is zero.
+#[inline]
+fn contains_zero_byte(x: usize) -> bool {
+    x.wrapping_sub(LO) & !x & HI != 0
+}
+
+/// Returns the index of the first occurrence of `byte` in `haystack`.
+///
+/// Scans a word at a time once the haystack is aligned, in the same way as
+/// `memchr`.
+pub(crate) fn memchr(byte: u8, haystack: &[u8]) -> Option<usize> {
+    let len = haystack.len();
+    let ptr = haystack.as_ptr();
+
+    // scan byte by byte up to the first aligned word
+    let mut off = cmp::min(ptr.align_offset(WORD), len);
+    if let Some(i) = haystack[..off].iter().position(|&b| b == byte) {
+        return Some(i);
+    }
+
+    let repeated = LO * byte as usize;
+    while off + WORD <= len {
+        // `off` is aligned and in bounds
+        let word = unsafe { *(ptr.add(off) as *const usize) };
+        if contains_zero_byte(word ^ repeated) {
+            break;
+        }
+        off += WORD;
+    }
+
+    haystack[off..]
+        .iter()
+        .position(|&b| b == byte)
+        .map(|i| off + i)
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (&first, rest) = match needle.split_first() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+
+    let mut off = 0;
+    while haystack.len() - off >= needle.len() {
+        let last_start = haystack.len() - needle.len();
+        let i = off + memchr(first, &haystack[off..=last_start])?;
+
+        if haystack[i + 1..i + needle.len()] == *rest {
+            return Some(i);
+        }
+        off = i + 1;
+    }
+
+    None
+}
+
+/// Reports that nothing was found in the `searched` first bytes of `buf`.
+fn not_found<B: Buf + ?Sized>(buf: &B, searched: usize) -> Result<Option<usize>, TryGetError> {
+    if searched < buf.remaining() {
+        // `for_each_chunk` did not reach the other bytes
+        return Err(TryGetError {
+            requested: buf.remaining(),
+            available: searched,
+        });
+    }
+
+    Ok(None)
+}
+
+pub(crate) fn find_byte<B: Buf + ?Sized>(buf: &B, byte: u8) -> Result<Option<usize>, TryGetError> {
+    match find_byte_from(buf, byte, 0) {
+        Ok(i) => Ok(Some(i)),
+        Err(searched) => not_found(buf, searched),
+    }
+}
+
+/// Returns the offset of the first occurrence of `byte` in `buf` at or after
+/// `start`, or the number of bytes searched if there is none.
+pub(crate) fn find_byte_from<B: Buf + ?Sized>(
+    buf: &B,
+    byte: u8,
+    start: usize,
+) -> Result<usize, usize> {
+    let mut off = 0;
+    let mut ret = None;
+
+    buf.for_each_chunk(&mut |chunk| {
+        let skip = cmp::min(start.saturating_sub(off), chunk.len());
+        match memchr(byte, &chunk[skip..]) {
+            Some(i) => {
+                ret = Some(off + skip + i);
+                false
+            }
+            None => {
+                off += chunk.len();
+                true
+            }
+        }
+    });
+
+    ret.ok_or(off)
+}
+
+pub(crate) fn find_slice<B: Buf + ?Sized>(
+    buf: &B,
+    needle: &[u8],
+) -> Result<Option<usize>, TryGetError> {
+    // fast path for contiguous buffers
+    let chunk = buf.chunk();
+    if chunk.len() == buf.remaining() {
+        return Ok(find(chunk, needle));
+    }
+
+    // Offset of the current chunk in `buf`.
+    let mut off = 0;
+    // The last `needle.len() - 1` bytes preceding the current chunk, to look
+    // for occurrences that straddle chunk boundaries.
+    let mut tail = Vec::new();
+    let mut ret = None;
+
+    buf.for_each_chunk(&mut |chunk| {
+        if !tail.is_empty() {
+            let tail_len = tail.len();
+            tail.extend_from_slice(&chunk[..cmp::min(chunk.len(), needle.len() - 1)]);
+
+            if let Some(i) = find(&tail, needle) {
+                if i < tail_len {
+                    ret = Some(off - tail_len + i);
+                    return false;
+                }
+            }
+
+            tail.truncate(tail_len);
+        }
+
+        if let Some(i) = find(chunk, needle) {
+            ret = Some(off + i);
+            return false;
+        }
+
+        off += chunk.len();
+
+        let keep = needle.len().saturating_sub(1);
+        if chunk.len() >= keep {
+            tail.clear();
+            tail.extend_from_slice(&chunk[chunk.len() - keep..]);
+        } else {
+            tail.extend_from_slice(chunk);
+            let excess = tail.len().saturating_sub(keep);
+            tail.drain(..excess);
+        }
+
+        true
+    });
+
+    match ret {
+        Some(i) => Ok(Some(i)),
+        None => not_found(buf, off),
+    }
+}
@@ -145,6 +145,15 @@ impl<T: Buf> Buf for Take<T> {
         self.limit -= cnt;
     }
 
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        let mut limit = self.limit;
+        self.inner.for_each_chunk(&mut |chunk| {
+            let chunk = &chunk[..cmp::min(chunk.len(), limit)];
+            limit -= chunk.len();
+            f(chunk) && limit > 0
+        });
+    }
+
     fn copy_to_bytes(&mut self, len: usize) -> Bytes {
         assert!(len <= self.remaining(), "`len` greater than remaining");
 
@@ -0,0 +1,138 @@
+use core::{cmp, fmt};
+
+use super::Buf;
+
+/// The longest encoding of a 64 bit value.
+pub(crate) const MAX_LEN_U64: usize = 10;
+
+/// The longest encoding of a 32 bit value.
+pub(crate) const MAX_LEN_U32: usize = 5;
+
+/// Error returned when decoding a LEB128 varint fails.
+///
+/// The buffer is left unchanged when this error is returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Buf;
+/// use bytes::buf::VarintError;
+///
+/// let mut buf = &b"\x80\x80"[..];
+/// assert_eq!(Err(VarintError::Truncated), buf.get_uvarint_u64());
+///
+/// let mut buf = &b"\x80\x80\x80\x80\x10"[..];
+/// assert_eq!(Err(VarintError::Overflow), buf.get_uvarint_u32());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    /// The buffer ended before the last byte of the varint, or before the end
+    /// of the data it is the length prefix of. This is also returned when the
+    /// rest of the varint cannot be read without advancing, as described in
+    /// `Buf::peek_slice`.
+    Truncated,
+
+    /// The encoded value does not fit in the requested integer type.
+    Overflow,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Truncated => f.write_str("varint is truncated"),
+            VarintError::Overflow => f.write_str("varint overflows the integer type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {}
+
+#[cfg(feature = "std")]
+impl From<VarintError> for std::io::Error {
+    fn from(error: VarintError) -> Self {
+        let kind = match error {
+            VarintError::Truncated => std::io::ErrorKind::UnexpectedEof,
+            VarintError::Overflow => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
+/// Decodes a varint of at most `max_len` bytes from the start of `src`.
+///
+/// `last_max` is the largest value the byte at `max_len - 1` may hold without
+/// overflowing the target type. Returns the value and the number of bytes
+/// it was encoded with.
+fn decode(src: &[u8], max_len: usize, last_max: u8) -> Result<(u64, usize), VarintError> {
+    let mut value = 0;
+
+    for (i, &byte) in src.iter().take(max_len).enumerate() {
+        if i == max_len - 1 && byte > last_max {
+            return Err(VarintError::Overflow);
+        }
+
+        value |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+
+    Err(VarintError::Truncated)
+}
+
+/// Decodes the varint at the current position of `buf` without advancing it.
+pub(crate) fn peek_uvarint<B: Buf + ?Sized>(
+    buf: &B,
+    max_len: usize,
+    last_max: u8,
+) -> Result<(u64, usize), VarintError> {
+    match decode(buf.chunk(), max_len, last_max) {
+        // the varint may continue in the next chunk
+        Err(VarintError::Truncated) if buf.remaining() > buf.chunk().len() => {
+            let mut tmp = [0; MAX_LEN_U64];
+            let tmp = &mut tmp[..cmp::min(buf.remaining(), max_len)];
+            // decode whatever could be read if the buffer cannot be looked
+            // ahead in
+            let len = match buf.peek_slice(tmp) {
+                Ok(()) => tmp.len(),
+                Err(err) => err.available,
+            };
+            decode(&tmp[..len], max_len, last_max)
+        }
+        res => res,
+    }
+}
+
+pub(crate) fn get_uvarint<B: Buf + ?Sized>(
+    buf: &mut B,
+    max_len: usize,
+    last_max: u8,
+) -> Result<u64, VarintError> {
+    let (value, len) = peek_uvarint(buf, max_len, last_max)?;
+    buf.advance(len);
+    Ok(value)
+}
+
+/// Writes `n` to the start of `dst`, returning the number of bytes used.
+pub(crate) fn encode(mut n: u64, dst: &mut [u8; MAX_LEN_U64]) -> usize {
+    let mut i = 0;
+
+    while n >= 0x80 {
+        dst[i] = (n as u8) | 0x80;
+        n >>= 7;
+        i += 1;
+    }
+
+    dst[i] = n as u8;
+    i + 1
+}
+
+pub(crate) fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+pub(crate) fn zigzag_decode(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
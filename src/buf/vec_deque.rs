@@ -1,5 +1,8 @@
 use alloc::collections::VecDeque;
 
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
 use super::Buf;
 
 impl Buf for VecDeque<u8> {
@@ -19,4 +22,27 @@ impl Buf for VecDeque<u8> {
     fn advance(&mut self, cnt: usize) {
         self.drain(..cnt);
     }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let (s1, s2) = self.as_slices();
+
+        let mut n = 0;
+        for (s, slot) in [s1, s2].iter().filter(|s| !s.is_empty()).zip(dst) {
+            *slot = IoSlice::new(s);
+            n += 1;
+        }
+        n
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        let (s1, s2) = self.as_slices();
+        if f(s1) {
+            f(s2);
+        }
+    }
 }
+
+// There is no `BufMut` implementation, as `VecDeque` offers no way to write
+// to its spare capacity for `chunk_mut`. `RingBuf` implements both `Buf` and
+// `BufMut` and can be used as an in-memory pipe instead.
@@ -0,0 +1,375 @@
+use core::borrow::Borrow;
+use core::ops::{Deref, RangeBounds};
+use core::{cmp, fmt, hash, str};
+
+use alloc::string::String;
+
+use crate::Bytes;
+
+/// A cheaply cloneable and sliceable chunk of UTF-8 text.
+///
+/// `ByteStr` is a `Bytes` whose contents are known to be valid UTF-8. It
+/// dereferences to `str`, and slicing or splitting it shares the underlying
+/// storage the same way `Bytes` does, so parsed tokens such as header values
+/// can be kept around without copying.
+///
+/// ```
+/// use bytes::{ByteStr, Bytes};
+///
+/// let mut line = ByteStr::from_utf8(Bytes::from_static(b"key=value")).unwrap();
+/// let key = line.split_to(3);
+///
+/// assert_eq!(key, "key");
+/// assert_eq!(line.trim_start_matches('='), "value");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteStr {
+    // Invariant: `bytes` contains valid UTF-8.
+    bytes: Bytes,
+}
+
+impl ByteStr {
+    /// Creates a new empty `ByteStr`.
+    ///
+    /// This will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let s = ByteStr::new();
+    /// assert_eq!(s, "");
+    /// ```
+    #[inline]
+    pub fn new() -> ByteStr {
+        ByteStr {
+            bytes: Bytes::new(),
+        }
+    }
+
+    /// Creates a new `ByteStr` from a static string.
+    ///
+    /// The returned `ByteStr` will point directly to the static string. There
+    /// is no allocating or copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let s = ByteStr::from_static("hello");
+    /// assert_eq!(s, "hello");
+    /// ```
+    #[inline]
+    #[cfg(not(all(loom, test)))]
+    pub const fn from_static(s: &'static str) -> ByteStr {
+        ByteStr {
+            bytes: Bytes::from_static(s.as_bytes()),
+        }
+    }
+
+    #[cfg(all(loom, test))]
+    pub fn from_static(s: &'static str) -> ByteStr {
+        ByteStr {
+            bytes: Bytes::from_static(s.as_bytes()),
+        }
+    }
+
+    /// Converts a `Bytes` into a `ByteStr` if it contains valid UTF-8.
+    ///
+    /// No bytes are copied. On failure the original `Bytes` is handed back
+    /// along with the error describing where validation stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{ByteStr, Bytes};
+    ///
+    /// let s = ByteStr::from_utf8(Bytes::from_static(b"hello")).unwrap();
+    /// assert_eq!(s, "hello");
+    ///
+    /// let (bytes, err) = ByteStr::from_utf8(Bytes::from_static(b"he\xffllo")).unwrap_err();
+    /// assert_eq!(bytes, &b"he\xffllo"[..]);
+    /// assert_eq!(err.valid_up_to(), 2);
+    /// ```
+    pub fn from_utf8(bytes: Bytes) -> Result<ByteStr, (Bytes, str::Utf8Error)> {
+        match str::from_utf8(&bytes) {
+            Ok(_) => Ok(ByteStr { bytes }),
+            Err(err) => Err((bytes, err)),
+        }
+    }
+
+    /// Converts a `Bytes` into a `ByteStr` without checking that it contains
+    /// valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8, as with `str::from_utf8_unchecked`.
+    #[inline]
+    pub unsafe fn from_utf8_unchecked(bytes: Bytes) -> ByteStr {
+        ByteStr { bytes }
+    }
+
+    /// Returns the contents of this `ByteStr` as a `&str`.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // `bytes` is always valid UTF-8
+        unsafe { str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Returns a reference to the underlying `Bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let s = ByteStr::from_static("hello");
+    /// assert_eq!(s.as_bytes(), &b"hello"[..]);
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Converts this `ByteStr` into the underlying `Bytes`.
+    ///
+    /// This does not copy. `Bytes::from` can be used in the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let s = ByteStr::from_static("hello");
+    /// assert_eq!(s.into_bytes(), &b"hello"[..]);
+    /// ```
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+
+    /// Returns a slice of self for the provided range.
+    ///
+    /// This will increment the reference count for the underlying memory and
+    /// return a new `ByteStr` handle set to the slice.
+    ///
+    /// This operation is `O(1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let a = ByteStr::from(String::from("grüße"));
+    /// let b = a.slice(2..6);
+    ///
+    /// assert_eq!(b, "üß");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Requires that `begin <= end` and `end <= self.len()`, and that both
+    /// lie on a `char` boundary, otherwise slicing will panic.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> ByteStr {
+        use core::ops::Bound;
+
+        let begin = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("out of range"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+
+        assert!(
+            self.is_char_boundary(begin),
+            "range start is not a char boundary: {}",
+            begin,
+        );
+        assert!(
+            self.is_char_boundary(end),
+            "range end is not a char boundary: {}",
+            end,
+        );
+
+        ByteStr {
+            bytes: self.bytes.slice(begin..end),
+        }
+    }
+
+    /// Splits the string into two at the given index.
+    ///
+    /// Afterwards `self` contains elements `[at, len)`, and the returned
+    /// `ByteStr` contains elements `[0, at)`.
+    ///
+    /// This is an `O(1)` operation that just increases the reference count
+    /// and sets a few indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteStr;
+    ///
+    /// let mut a = ByteStr::from_static("héllo");
+    /// let b = a.split_to(3);
+    ///
+    /// assert_eq!(a, "llo");
+    /// assert_eq!(b, "hé");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len` or if `at` is not on a `char` boundary.
+    #[must_use = "consider ByteStr::advance if you don't need the other half"]
+    pub fn split_to(&mut self, at: usize) -> ByteStr {
+        assert!(
+            self.is_char_boundary(at),
+            "split_to index is not a char boundary: {}",
+            at,
+        );
+
+        ByteStr {
+            bytes: self.bytes.split_to(at),
+        }
+    }
+
+    /// Removes the first `cnt` bytes of the string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cnt > len` or if `cnt` is not on a `char` boundary.
+    pub fn advance(&mut self, cnt: usize) {
+        assert!(
+            self.is_char_boundary(cnt),
+            "advance index is not a char boundary: {}",
+            cnt,
+        );
+
+        crate::Buf::advance(&mut self.bytes, cnt);
+    }
+}
+
+impl Deref for ByteStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ByteStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for ByteStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Borrow<str> for ByteStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl hash::Hash for ByteStr {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        // must agree with `str` for `Borrow<str>`
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for ByteStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ByteStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<str> for ByteStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<ByteStr> for str {
+    fn eq(&self, other: &ByteStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<&str> for ByteStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<ByteStr> for &str {
+    fn eq(&self, other: &ByteStr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<String> for ByteStr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<ByteStr> for String {
+    fn eq(&self, other: &ByteStr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialOrd<str> for ByteStr {
+    fn partial_cmp(&self, other: &str) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<ByteStr> for str {
+    fn partial_cmp(&self, other: &ByteStr) -> Option<cmp::Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl From<&'static str> for ByteStr {
+    fn from(s: &'static str) -> ByteStr {
+        ByteStr::from_static(s)
+    }
+}
+
+impl From<String> for ByteStr {
+    fn from(s: String) -> ByteStr {
+        ByteStr {
+            bytes: Bytes::from(s),
+        }
+    }
+}
+
+impl From<ByteStr> for Bytes {
+    fn from(s: ByteStr) -> Bytes {
+        s.bytes
+    }
+}
@@ -0,0 +1,462 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::ops::{Deref, DerefMut};
+use core::{cmp, fmt, hash, str};
+
+use alloc::string::String;
+
+use crate::{ByteStr, BytesMut};
+
+/// A unique reference to a growable UTF-8 string.
+///
+/// `ByteString` is to `ByteStr` what `BytesMut` is to `Bytes`. Text is
+/// appended with [`push_str`], [`push`] or `write!`, finished prefixes can be
+/// split off with [`split_to`] without copying, and the whole buffer can be
+/// frozen into a [`ByteStr`].
+///
+/// ```
+/// use bytes::ByteString;
+/// use std::fmt::Write;
+///
+/// let mut buf = ByteString::with_capacity(64);
+/// buf.push_str("status: ");
+/// write!(buf, "{}", 200).unwrap();
+/// buf.push('\n');
+///
+/// let line = buf.split_to(buf.len()).freeze();
+/// assert_eq!(line, "status: 200\n");
+/// assert_eq!(buf, "");
+/// ```
+///
+/// [`push_str`]: #method.push_str
+/// [`push`]: #method.push
+/// [`split_to`]: #method.split_to
+/// [`ByteStr`]: struct.ByteStr.html
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ByteString {
+    // Invariant: `bytes` contains valid UTF-8.
+    bytes: BytesMut,
+}
+
+impl ByteString {
+    /// Creates a new `ByteString` with default capacity.
+    ///
+    /// Resulting object has length 0 and unspecified capacity.
+    /// This function does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let mut s = ByteString::new();
+    /// s.push_str("hello");
+    ///
+    /// assert_eq!(s, "hello");
+    /// ```
+    #[inline]
+    pub fn new() -> ByteString {
+        ByteString {
+            bytes: BytesMut::new(),
+        }
+    }
+
+    /// Creates a new `ByteString` with the specified capacity.
+    ///
+    /// The returned `ByteString` will be able to hold at least `capacity`
+    /// bytes without reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let s = ByteString::with_capacity(64);
+    ///
+    /// assert_eq!(s.len(), 0);
+    /// assert!(s.capacity() >= 64);
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> ByteString {
+        ByteString {
+            bytes: BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// Converts a `BytesMut` into a `ByteString` if it contains valid UTF-8.
+    ///
+    /// No bytes are copied. On failure the original `BytesMut` is handed back
+    /// along with the error describing where validation stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{ByteString, BytesMut};
+    ///
+    /// let s = ByteString::from_utf8(BytesMut::from("hello")).unwrap();
+    /// assert_eq!(s, "hello");
+    ///
+    /// let (bytes, err) = ByteString::from_utf8(BytesMut::from(&b"\xff"[..])).unwrap_err();
+    /// assert_eq!(bytes, &b"\xff"[..]);
+    /// assert_eq!(err.valid_up_to(), 0);
+    /// ```
+    pub fn from_utf8(bytes: BytesMut) -> Result<ByteString, (BytesMut, str::Utf8Error)> {
+        match str::from_utf8(&bytes) {
+            Ok(_) => Ok(ByteString { bytes }),
+            Err(err) => Err((bytes, err)),
+        }
+    }
+
+    /// Converts a `BytesMut` into a `ByteString` without checking that it
+    /// contains valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8, as with `str::from_utf8_unchecked`.
+    #[inline]
+    pub unsafe fn from_utf8_unchecked(bytes: BytesMut) -> ByteString {
+        ByteString { bytes }
+    }
+
+    /// Returns the contents of this `ByteString` as a `&str`.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // `bytes` is always valid UTF-8
+        unsafe { str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Returns the contents of this `ByteString` as a `&mut str`.
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        // `bytes` is always valid UTF-8, and `&mut str` cannot break that
+        unsafe { str::from_utf8_unchecked_mut(&mut self.bytes) }
+    }
+
+    /// Returns a shared reference to the underlying `BytesMut`.
+    ///
+    /// No `&mut BytesMut` accessor is provided, as it could be used to break
+    /// the UTF-8 invariant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let s = ByteString::from("hello");
+    /// assert_eq!(&s.as_inner()[..], b"hello");
+    /// ```
+    #[inline]
+    pub fn as_inner(&self) -> &BytesMut {
+        &self.bytes
+    }
+
+    /// Converts this `ByteString` into the underlying `BytesMut`.
+    #[inline]
+    pub fn into_bytes_mut(self) -> BytesMut {
+        self.bytes
+    }
+
+    /// Returns the number of bytes the `ByteString` can hold without
+    /// reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// See [`BytesMut::reserve`] for how the existing allocation is reused.
+    ///
+    /// [`BytesMut::reserve`]: struct.BytesMut.html#method.reserve
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.bytes.reserve(additional);
+    }
+
+    /// Appends a string slice to the end of this `ByteString`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let mut s = ByteString::from("foo");
+    /// s.push_str("bar");
+    ///
+    /// assert_eq!(s, "foobar");
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    /// Appends a `char` to the end of this `ByteString`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let mut s = ByteString::from("ab");
+    /// s.push('ç');
+    ///
+    /// assert_eq!(s, "abç");
+    /// ```
+    #[inline]
+    pub fn push(&mut self, ch: char) {
+        let mut tmp = [0; 4];
+        self.push_str(ch.encode_utf8(&mut tmp));
+    }
+
+    /// Shortens the string, keeping the first `len` bytes and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than the string's current length, this has no
+    /// effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is not on a `char` boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            assert!(
+                self.is_char_boundary(len),
+                "truncate index is not a char boundary: {}",
+                len,
+            );
+            self.bytes.truncate(len);
+        }
+    }
+
+    /// Clears the string, removing all data.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Splits the string into two at the given index.
+    ///
+    /// Afterwards `self` contains elements `[at, len)`, and the returned
+    /// `ByteString` contains elements `[0, at)`.
+    ///
+    /// This is an `O(1)` operation that just increases the reference count
+    /// and sets a few indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let mut a = ByteString::from("née\nnext");
+    /// let b = a.split_to(5);
+    ///
+    /// assert_eq!(a, "next");
+    /// assert_eq!(b, "née\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len` or if `at` is not on a `char` boundary.
+    #[must_use]
+    pub fn split_to(&mut self, at: usize) -> ByteString {
+        assert!(
+            self.is_char_boundary(at),
+            "split_to index is not a char boundary: {}",
+            at,
+        );
+
+        ByteString {
+            bytes: self.bytes.split_to(at),
+        }
+    }
+
+    /// Converts `self` into an immutable `ByteStr`.
+    ///
+    /// The conversion is zero cost and the result is known to hold valid
+    /// UTF-8, so it is not validated again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::ByteString;
+    ///
+    /// let mut s = ByteString::new();
+    /// s.push_str("hello");
+    ///
+    /// let frozen = s.freeze();
+    /// assert_eq!(frozen, "hello");
+    /// ```
+    #[inline]
+    pub fn freeze(self) -> ByteStr {
+        unsafe { ByteStr::from_utf8_unchecked(self.bytes.freeze()) }
+    }
+}
+
+impl Deref for ByteString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl DerefMut for ByteString {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl AsRef<str> for ByteString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for ByteString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Borrow<str> for ByteString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl BorrowMut<str> for ByteString {
+    fn borrow_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl hash::Hash for ByteString {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        // must agree with `str` for `Borrow<str>`
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for ByteString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ByteString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Write for ByteString {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, ch: char) -> fmt::Result {
+        self.push(ch);
+        Ok(())
+    }
+}
+
+impl Extend<char> for ByteString {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        for ch in iter {
+            self.push(ch);
+        }
+    }
+}
+
+impl<'a> Extend<&'a str> for ByteString {
+    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+        for s in iter {
+            self.push_str(s);
+        }
+    }
+}
+
+impl PartialEq<str> for ByteString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<ByteString> for str {
+    fn eq(&self, other: &ByteString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<&str> for ByteString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<ByteString> for &str {
+    fn eq(&self, other: &ByteString) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<String> for ByteString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<ByteString> for String {
+    fn eq(&self, other: &ByteString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialOrd<str> for ByteString {
+    fn partial_cmp(&self, other: &str) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<ByteString> for str {
+    fn partial_cmp(&self, other: &ByteString) -> Option<cmp::Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl<'a> From<&'a str> for ByteString {
+    fn from(s: &'a str) -> ByteString {
+        ByteString {
+            bytes: BytesMut::from(s),
+        }
+    }
+}
+
+impl From<String> for ByteString {
+    fn from(s: String) -> ByteString {
+        ByteString {
+            bytes: BytesMut::from_vec(s.into_bytes()),
+        }
+    }
+}
+
+impl From<ByteString> for BytesMut {
+    fn from(s: ByteString) -> BytesMut {
+        s.bytes
+    }
+}
+
+impl From<ByteString> for ByteStr {
+    fn from(s: ByteString) -> ByteStr {
+        s.freeze()
+    }
+}
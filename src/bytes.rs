@@ -10,11 +10,12 @@ use alloc::{
     vec::Vec,
 };
 
-use crate::buf::IntoIter;
+use crate::buf::{search, IntoIter};
 #[allow(unused)]
 use crate::loom::sync::atomic::AtomicMut;
 use crate::loom::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use crate::Buf;
+use crate::split::{self, ChunksExact, Split, SplitN, SplitWhitespace};
+use crate::{Buf, BytesMut, TryReserveError};
 
 /// A cheaply cloneable and sliceable chunk of contiguous memory.
 ///
@@ -113,9 +114,88 @@ pub(crate) struct Vtable {
     /// takes `Bytes` to value
     pub to_vec: unsafe fn(&AtomicPtr<()>, *const u8, usize) -> Vec<u8>,
     /// fn(data, ptr, len)
+    ///
+    /// takes `Bytes` to value if it returns `Some`, and leaves it untouched
+    /// otherwise
+    pub try_to_mut: unsafe fn(&AtomicPtr<()>, *const u8, usize) -> Option<BytesMut>,
+    /// fn(data)
+    pub is_unique: unsafe fn(&AtomicPtr<()>) -> bool,
+    /// fn(data, ptr, len)
+    pub info: unsafe fn(&AtomicPtr<()>, *const u8, usize) -> BytesInfo,
+    /// fn(data, ptr, len)
+    pub downgrade: unsafe fn(&AtomicPtr<()>, *const u8, usize) -> WeakBytes,
+    /// fn(data, ptr, len)
     pub drop: unsafe fn(&mut AtomicPtr<()>, *const u8, usize),
 }
 
+/// A weak reference to the buffer of a [`Bytes`] handle.
+///
+/// `WeakBytes` is created by [`Bytes::downgrade`]. It refers to the same view
+/// of the same buffer as the `Bytes` it was created from, but does not keep
+/// the buffer alive: once every `Bytes` handle sharing the buffer has been
+/// dropped, the memory is released and [`upgrade`] returns `None`.
+///
+/// This mirrors `std::sync::Weak` for `std::sync::Arc`.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+///
+/// let b = Bytes::from(b"hello world".to_vec());
+/// let weak = b.downgrade();
+///
+/// assert_eq!(weak.upgrade(), Some(b.clone()));
+///
+/// drop(b);
+/// assert_eq!(weak.upgrade(), None);
+/// ```
+///
+/// [`Bytes`]: struct.Bytes.html
+/// [`Bytes::downgrade`]: struct.Bytes.html#method.downgrade
+/// [`upgrade`]: #method.upgrade
+pub struct WeakBytes {
+    ptr: *const u8,
+    len: usize,
+    data: *mut (),
+    vtable: &'static WeakVtable,
+}
+
+pub(crate) struct WeakVtable {
+    /// fn(data, ptr, len)
+    pub upgrade: unsafe fn(*mut (), *const u8, usize) -> Option<Bytes>,
+    /// fn(data)
+    ///
+    /// increments the weak count
+    pub clone: unsafe fn(*mut ()),
+    /// fn(data)
+    pub drop: unsafe fn(*mut ()),
+}
+
+/// A snapshot of the storage backing a [`Bytes`] handle.
+///
+/// Returned by [`Bytes::info`]. This is intended for diagnostics, such as
+/// finding out which handles keep a large buffer alive. The values are
+/// captured when `info` is called and may be outdated by the time they are
+/// inspected if other handles are cloned or dropped concurrently.
+///
+/// [`Bytes`]: struct.Bytes.html
+/// [`Bytes::info`]: struct.Bytes.html#method.info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BytesInfo {
+    kind: Kind,
+    ref_count: Option<usize>,
+    capacity: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Static,
+    Vec,
+    Shared,
+    Owned,
+}
+
 impl Bytes {
     /// Creates a new empty `Bytes`.
     ///
@@ -213,6 +293,81 @@ impl Bytes {
         data.to_vec().into()
     }
 
+    /// Tries to create a `Bytes` instance from slice, by copying it.
+    ///
+    /// An error is returned instead of aborting if the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let b = Bytes::try_copy_from_slice(b"hello").unwrap();
+    /// assert_eq!(b, &b"hello"[..]);
+    /// ```
+    pub fn try_copy_from_slice(data: &[u8]) -> Result<Self, TryReserveError> {
+        let mut buf = BytesMut::try_with_capacity(data.len())?;
+        buf.extend_from_slice(data);
+        Ok(buf.freeze())
+    }
+
+    /// Creates a new `Bytes` backed by the given owner, without copying.
+    ///
+    /// The `owner` is moved into a reference counted allocation, and the
+    /// returned `Bytes` points directly at the slice returned by
+    /// `owner.as_ref()`. Cloning and slicing the returned handle only
+    /// increments the reference count, and `owner` is dropped once the last
+    /// handle referring to it goes away.
+    ///
+    /// This makes it possible to expose memory that is not managed by a
+    /// `Vec<u8>`, such as a memory-mapped file or a pooled buffer, as `Bytes`.
+    ///
+    /// The slice returned by `owner.as_ref()` must stay the same for as long
+    /// as `owner` is alive. It is only queried once, when the `Bytes` is
+    /// created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use std::sync::Arc;
+    ///
+    /// let owner: Arc<[u8]> = Arc::from(&b"hello world"[..]);
+    /// let b = Bytes::from_owner(owner.clone());
+    ///
+    /// assert_eq!(&b[..], b"hello world");
+    /// assert_eq!(b.as_ptr(), owner.as_ptr());
+    ///
+    /// let hello = b.slice(..5);
+    /// drop(b);
+    /// assert_eq!(&hello[..], b"hello");
+    /// ```
+    pub fn from_owner<T>(owner: T) -> Bytes
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        let owned = Box::into_raw(Box::new(Owned {
+            lifetime: OwnedLifetime {
+                ref_cnt: AtomicUsize::new(1),
+                weak_cnt: AtomicUsize::new(1),
+                drop: owned_drop_in_place::<T>,
+                dealloc: owned_dealloc::<T>,
+            },
+            owner,
+        }));
+
+        // The slice is borrowed from the boxed owner, whose address is
+        // stable until the last handle drops it.
+        let buf = unsafe { (*owned).owner.as_ref() };
+
+        Bytes {
+            ptr: buf.as_ptr(),
+            len: buf.len(),
+            data: AtomicPtr::new(owned.cast()),
+            vtable: &OWNED_VTABLE,
+        }
+    }
+
     /// Returns a slice of self for the provided range.
     ///
     /// This will increment the reference count for the underlying memory and
@@ -432,6 +587,136 @@ impl Bytes {
         ret
     }
 
+    /// Splits off the bytes up to the first occurrence of `delimiter`.
+    ///
+    /// If `delimiter` is found, the bytes preceding it are returned and
+    /// `self` is advanced past the delimiter, which is dropped. Otherwise
+    /// `None` is returned and `self` is left unchanged.
+    ///
+    /// This is an `O(1)` operation on top of the search, that just increases
+    /// the reference count and sets a few indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let mut a = Bytes::from(&b"PING\r\nPONG"[..]);
+    ///
+    /// assert_eq!(a.split_to_delimiter(b"\r\n").unwrap(), &b"PING"[..]);
+    /// assert_eq!(a.split_to_delimiter(b"\r\n"), None);
+    /// assert_eq!(&a[..], b"PONG");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` is empty.
+    pub fn split_to_delimiter(&mut self, delimiter: &[u8]) -> Option<Bytes> {
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+
+        let at = search::find(self, delimiter)?;
+        let ret = self.split_to(at);
+        self.advance(delimiter.len());
+        Some(ret)
+    }
+
+    /// Returns an iterator over the sub-slices separated by bytes that match
+    /// `pred`, as `Bytes` sharing the storage of `self`.
+    ///
+    /// The matched bytes are not contained in the sub-slices. This behaves
+    /// like `<[u8]>::split`, but each item is an `O(1)` `Bytes` handle rather
+    /// than a borrowed slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let bytes = Bytes::from_static(b"a,b,,c");
+    /// let fields: Vec<Bytes> = bytes.split(|&b| b == b',').collect();
+    ///
+    /// assert_eq!(fields, [&b"a"[..], &b"b"[..], &b""[..], &b"c"[..]]);
+    /// ```
+    pub fn split<F>(&self, pred: F) -> Split<F>
+    where
+        F: FnMut(&u8) -> bool,
+    {
+        split::split(self.clone(), pred)
+    }
+
+    /// Returns an iterator over at most `n` sub-slices separated by bytes that
+    /// match `pred`, as `Bytes` sharing the storage of `self`.
+    ///
+    /// The last sub-slice contains the remainder of `self`. This behaves like
+    /// `<[u8]>::splitn`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let bytes = Bytes::from_static(b"Host: example.com:8080");
+    /// let mut parts = bytes.splitn(2, |&b| b == b':');
+    ///
+    /// assert_eq!(parts.next().unwrap(), &b"Host"[..]);
+    /// assert_eq!(parts.next().unwrap(), &b" example.com:8080"[..]);
+    /// assert_eq!(parts.next(), None);
+    /// ```
+    pub fn splitn<F>(&self, n: usize, pred: F) -> SplitN<F>
+    where
+        F: FnMut(&u8) -> bool,
+    {
+        split::splitn(self.clone(), n, pred)
+    }
+
+    /// Returns an iterator over the sub-slices separated by ASCII whitespace,
+    /// as `Bytes` sharing the storage of `self`.
+    ///
+    /// Empty sub-slices, from leading, trailing or repeated whitespace, are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let bytes = Bytes::from_static(b" GET  /index.html\tHTTP/1.1\r\n");
+    /// let words: Vec<Bytes> = bytes.split_whitespace().collect();
+    ///
+    /// assert_eq!(words, [&b"GET"[..], &b"/index.html"[..], &b"HTTP/1.1"[..]]);
+    /// ```
+    pub fn split_whitespace(&self) -> SplitWhitespace {
+        split::split_whitespace(self.clone())
+    }
+
+    /// Returns an iterator over `chunk_size` bytes at a time, as `Bytes`
+    /// sharing the storage of `self`.
+    ///
+    /// If `chunk_size` does not divide the length of `self`, the last up to
+    /// `chunk_size - 1` bytes are left out and can be retrieved with
+    /// `ChunksExact::remainder`. This behaves like `<[u8]>::chunks_exact`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let bytes = Bytes::from_static(b"abcdefg");
+    /// let mut chunks = bytes.chunks_exact(3);
+    ///
+    /// assert_eq!(chunks.next().unwrap(), &b"abc"[..]);
+    /// assert_eq!(chunks.next().unwrap(), &b"def"[..]);
+    /// assert_eq!(chunks.next(), None);
+    /// assert_eq!(chunks.remainder(), &b"g"[..]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact {
+        split::chunks_exact(self.clone(), chunk_size)
+    }
+
     /// Shortens the buffer, keeping the first `len` bytes and dropping the
     /// rest.
     ///
@@ -484,6 +769,131 @@ impl Bytes {
         self.truncate(0);
     }
 
+    /// Returns true if this is the only handle to the underlying buffer.
+    ///
+    /// When this returns true, [`try_into_mut`] succeeds without copying.
+    /// Weak references created by [`downgrade`] are not counted.
+    ///
+    /// Always returns false if the data is backed by a static slice or by an
+    /// owner passed to [`from_owner`].
+    ///
+    /// Other handles may be cloned or dropped concurrently on other threads,
+    /// so the result is only stable if no other handle can be reached from
+    /// another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let a = Bytes::from(vec![1, 2, 3]);
+    /// assert!(a.is_unique());
+    /// let b = a.clone();
+    /// assert!(!a.is_unique());
+    /// drop(b);
+    /// assert!(a.is_unique());
+    /// ```
+    ///
+    /// [`try_into_mut`]: #method.try_into_mut
+    /// [`downgrade`]: #method.downgrade
+    /// [`from_owner`]: #method.from_owner
+    pub fn is_unique(&self) -> bool {
+        unsafe { (self.vtable.is_unique)(&self.data) }
+    }
+
+    /// Returns a snapshot of the storage backing this handle.
+    ///
+    /// The snapshot tells how the buffer is stored, how many handles
+    /// currently share it and how large it is. This can be used to detect
+    /// small handles that keep a much larger buffer alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let buf = Bytes::from(vec![0; 1024]);
+    /// let small = buf.slice(..8);
+    /// drop(buf);
+    ///
+    /// let info = small.info();
+    /// assert!(info.is_shared());
+    /// assert_eq!(info.ref_count(), Some(1));
+    /// assert_eq!(info.capacity(), Some(1024));
+    /// ```
+    pub fn info(&self) -> BytesInfo {
+        unsafe { (self.vtable.info)(&self.data, self.ptr, self.len) }
+    }
+
+    /// Creates a [`WeakBytes`] referring to the same view of the same buffer
+    /// as `self`.
+    ///
+    /// The weak reference does not keep the buffer alive. If `self` still
+    /// owns the vector it was created from, the vector is first moved into
+    /// reference counted storage, as happens when `self` is cloned.
+    ///
+    /// Static slices are never released, so a weak reference to one can
+    /// always be upgraded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let b = Bytes::from(b"hello".to_vec());
+    /// let weak = b.downgrade();
+    /// assert_eq!(weak.upgrade().unwrap(), b"hello"[..]);
+    ///
+    /// drop(b);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    ///
+    /// [`WeakBytes`]: struct.WeakBytes.html
+    pub fn downgrade(&self) -> WeakBytes {
+        unsafe { (self.vtable.downgrade)(&self.data, self.ptr, self.len) }
+    }
+
+    /// Try to convert self into `BytesMut`.
+    ///
+    /// If `self` is the only handle to its underlying buffer, this will
+    /// succeed and return a `BytesMut` with the contents of `self`, reusing
+    /// the buffer without copying. Otherwise `self` is returned unchanged.
+    ///
+    /// Outstanding [`WeakBytes`] do not prevent the conversion, but can no
+    /// longer be upgraded once it succeeded.
+    ///
+    /// Handles created with `Bytes::from_static` or `Bytes::from_owner` never
+    /// own their buffer uniquely, so this always fails for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Bytes, BytesMut};
+    ///
+    /// let bytes = Bytes::from(b"hello".to_vec());
+    /// assert_eq!(bytes.try_into_mut(), Ok(BytesMut::from(&b"hello"[..])));
+    ///
+    /// let a = Bytes::from(b"hello".to_vec());
+    /// let b = a.clone();
+    /// let a = a.try_into_mut().unwrap_err();
+    ///
+    /// drop(b);
+    /// assert!(a.try_into_mut().is_ok());
+    /// ```
+    ///
+    /// [`WeakBytes`]: struct.WeakBytes.html
+    pub fn try_into_mut(self) -> Result<BytesMut, Bytes> {
+        // `is_unique` is not enough here, a `WeakBytes` may be upgraded
+        // between the check and the conversion.
+        match unsafe { (self.vtable.try_to_mut)(&self.data, self.ptr, self.len) } {
+            Some(b) => {
+                mem::forget(self);
+                Ok(b)
+            }
+            None => Err(self),
+        }
+    }
+
     #[inline]
     pub(crate) unsafe fn with_vtable(
         ptr: *const u8,
@@ -856,6 +1266,142 @@ impl From<Bytes> for Vec<u8> {
     }
 }
 
+// ===== impl WeakBytes =====
+
+impl WeakBytes {
+    /// Attempts to upgrade the weak reference to a `Bytes`.
+    ///
+    /// Returns `None` if every `Bytes` handle sharing the buffer has been
+    /// dropped, or if the buffer has been taken over by
+    /// `Bytes::try_into_mut` or converted into a `Vec<u8>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let mut b = Bytes::from(b"hello world".to_vec());
+    /// let weak = b.slice(6..).downgrade();
+    ///
+    /// b.truncate(5);
+    /// assert_eq!(weak.upgrade().unwrap(), b"world"[..]);
+    ///
+    /// drop(b);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn upgrade(&self) -> Option<Bytes> {
+        unsafe { (self.vtable.upgrade)(self.data, self.ptr, self.len) }
+    }
+
+    /// Returns the length of the view this weak reference refers to.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this weak reference refers to an empty view.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub(crate) unsafe fn with_vtable(
+        ptr: *const u8,
+        len: usize,
+        data: *mut (),
+        vtable: &'static WeakVtable,
+    ) -> WeakBytes {
+        WeakBytes {
+            ptr,
+            len,
+            data,
+            vtable,
+        }
+    }
+}
+
+// WeakVtable must enforce this behavior
+unsafe impl Send for WeakBytes {}
+unsafe impl Sync for WeakBytes {}
+
+impl Clone for WeakBytes {
+    fn clone(&self) -> WeakBytes {
+        unsafe { (self.vtable.clone)(self.data) };
+
+        WeakBytes {
+            ptr: self.ptr,
+            len: self.len,
+            data: self.data,
+            vtable: self.vtable,
+        }
+    }
+}
+
+impl Drop for WeakBytes {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.drop)(self.data) }
+    }
+}
+
+impl fmt::Debug for WeakBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakBytes").field("len", &self.len).finish()
+    }
+}
+
+// ===== impl BytesInfo =====
+
+impl BytesInfo {
+    pub(crate) fn shared(ref_count: usize, capacity: usize) -> BytesInfo {
+        BytesInfo {
+            kind: Kind::Shared,
+            ref_count: Some(ref_count),
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Returns true if the handle points to a static slice, as created by
+    /// `Bytes::from_static`.
+    pub fn is_static(&self) -> bool {
+        self.kind == Kind::Static
+    }
+
+    /// Returns true if the handle still owns the vector or boxed slice it
+    /// was created from, which has not been shared with any other handle
+    /// yet.
+    pub fn is_vec(&self) -> bool {
+        self.kind == Kind::Vec
+    }
+
+    /// Returns true if the buffer is held in reference counted storage,
+    /// which happens once a handle created from a vector is cloned or split,
+    /// or when a `BytesMut` is frozen after being split.
+    pub fn is_shared(&self) -> bool {
+        self.kind == Kind::Shared
+    }
+
+    /// Returns true if the handle was created by `Bytes::from_owner`.
+    pub fn is_owned(&self) -> bool {
+        self.kind == Kind::Owned
+    }
+
+    /// Returns the number of handles sharing the buffer.
+    ///
+    /// Returns `None` for static slices, which are not reference counted.
+    pub fn ref_count(&self) -> Option<usize> {
+        self.ref_count
+    }
+
+    /// Returns the size in bytes of the allocation backing the buffer.
+    ///
+    /// This is the memory that is kept alive for as long as any of the
+    /// handles sharing it exists, regardless of how small their views are.
+    /// Returns `None` for static slices and for buffers created by
+    /// `Bytes::from_owner`, whose size is not tracked.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+}
+
 // ===== impl Vtable =====
 
 impl fmt::Debug for Vtable {
@@ -872,6 +1418,10 @@ impl fmt::Debug for Vtable {
 const STATIC_VTABLE: Vtable = Vtable {
     clone: static_clone,
     to_vec: static_to_vec,
+    try_to_mut: static_try_to_mut,
+    is_unique: static_is_unique,
+    info: static_info,
+    downgrade: static_downgrade,
     drop: static_drop,
 };
 
@@ -885,21 +1435,68 @@ unsafe fn static_to_vec(_: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Vec<u8
     slice.to_vec()
 }
 
+unsafe fn static_try_to_mut(_: &AtomicPtr<()>, _: *const u8, _: usize) -> Option<BytesMut> {
+    None
+}
+
+unsafe fn static_is_unique(_: &AtomicPtr<()>) -> bool {
+    false
+}
+
+unsafe fn static_info(_: &AtomicPtr<()>, _: *const u8, _: usize) -> BytesInfo {
+    BytesInfo {
+        kind: Kind::Static,
+        ref_count: None,
+        capacity: None,
+    }
+}
+
+unsafe fn static_downgrade(_: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    WeakBytes::with_vtable(ptr, len, ptr::null_mut(), &STATIC_WEAK_VTABLE)
+}
+
 unsafe fn static_drop(_: &mut AtomicPtr<()>, _: *const u8, _: usize) {
     // nothing to drop for &'static [u8]
 }
 
+static STATIC_WEAK_VTABLE: WeakVtable = WeakVtable {
+    upgrade: static_upgrade,
+    clone: static_weak_clone,
+    drop: static_weak_drop,
+};
+
+unsafe fn static_upgrade(_: *mut (), ptr: *const u8, len: usize) -> Option<Bytes> {
+    let slice = slice::from_raw_parts(ptr, len);
+    Some(Bytes::from_static(slice))
+}
+
+unsafe fn static_weak_clone(_: *mut ()) {
+    // nothing to count for &'static [u8]
+}
+
+unsafe fn static_weak_drop(_: *mut ()) {
+    // nothing to drop for &'static [u8]
+}
+
 // ===== impl PromotableVtable =====
 
 static PROMOTABLE_EVEN_VTABLE: Vtable = Vtable {
     clone: promotable_even_clone,
     to_vec: promotable_even_to_vec,
+    try_to_mut: promotable_even_try_to_mut,
+    is_unique: promotable_is_unique,
+    info: promotable_even_info,
+    downgrade: promotable_even_downgrade,
     drop: promotable_even_drop,
 };
 
 static PROMOTABLE_ODD_VTABLE: Vtable = Vtable {
     clone: promotable_odd_clone,
     to_vec: promotable_odd_to_vec,
+    try_to_mut: promotable_odd_try_to_mut,
+    is_unique: promotable_is_unique,
+    info: promotable_odd_info,
+    downgrade: promotable_odd_downgrade,
     drop: promotable_odd_drop,
 };
 
@@ -942,12 +1539,84 @@ unsafe fn promotable_to_vec(
     }
 }
 
+unsafe fn promotable_try_to_mut(
+    data: &AtomicPtr<()>,
+    ptr: *const u8,
+    len: usize,
+    f: fn(*mut ()) -> *mut u8,
+) -> Option<BytesMut> {
+    let shared = data.load(Ordering::Acquire);
+    let kind = shared as usize & KIND_MASK;
+
+    if kind == KIND_ARC {
+        shared_try_to_mut_impl(shared.cast(), ptr, len)
+    } else {
+        // The `Bytes` holds the only handle to the boxed slice, and its view
+        // always extends to the end of the allocation, so the whole slice can
+        // be handed over to a `BytesMut` starting at the same offset.
+        debug_assert_eq!(kind, KIND_VEC);
+
+        let buf = f(shared);
+        let off = ptr as usize - buf as usize;
+        let cap = off + len;
+
+        let mut b = BytesMut::from_vec(Vec::from_raw_parts(buf, cap, cap));
+        b.set_start(off);
+        Some(b)
+    }
+}
+
 unsafe fn promotable_even_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Vec<u8> {
     promotable_to_vec(data, ptr, len, |shared| {
         ptr_map(shared.cast(), |addr| addr & !KIND_MASK)
     })
 }
 
+unsafe fn promotable_even_try_to_mut(
+    data: &AtomicPtr<()>,
+    ptr: *const u8,
+    len: usize,
+) -> Option<BytesMut> {
+    promotable_try_to_mut(data, ptr, len, |shared| {
+        ptr_map(shared.cast(), |addr| addr & !KIND_MASK)
+    })
+}
+
+unsafe fn promotable_info(
+    data: &AtomicPtr<()>,
+    ptr: *const u8,
+    len: usize,
+    f: fn(*mut ()) -> *mut u8,
+) -> BytesInfo {
+    let shared = data.load(Ordering::Acquire);
+    let kind = shared as usize & KIND_MASK;
+
+    if kind == KIND_ARC {
+        shared_info_impl(shared.cast())
+    } else {
+        debug_assert_eq!(kind, KIND_VEC);
+
+        let buf = f(shared);
+
+        BytesInfo {
+            kind: Kind::Vec,
+            ref_count: Some(1),
+            capacity: Some((ptr as usize - buf as usize) + len),
+        }
+    }
+}
+
+unsafe fn promotable_even_info(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> BytesInfo {
+    promotable_info(data, ptr, len, |shared| {
+        ptr_map(shared.cast(), |addr| addr & !KIND_MASK)
+    })
+}
+
+unsafe fn promotable_even_downgrade(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    // Cloning promotes the vec to `Shared`, which supports weak references.
+    promotable_even_clone(data, ptr, len).downgrade()
+}
+
 unsafe fn promotable_even_drop(data: &mut AtomicPtr<()>, ptr: *const u8, len: usize) {
     data.with_mut(|shared| {
         let shared = *shared;
@@ -979,6 +1648,23 @@ unsafe fn promotable_odd_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize
     promotable_to_vec(data, ptr, len, |shared| shared.cast())
 }
 
+unsafe fn promotable_odd_try_to_mut(
+    data: &AtomicPtr<()>,
+    ptr: *const u8,
+    len: usize,
+) -> Option<BytesMut> {
+    promotable_try_to_mut(data, ptr, len, |shared| shared.cast())
+}
+
+unsafe fn promotable_odd_info(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> BytesInfo {
+    promotable_info(data, ptr, len, |shared| shared.cast())
+}
+
+unsafe fn promotable_odd_downgrade(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    // Cloning promotes the vec to `Shared`, which supports weak references.
+    promotable_odd_clone(data, ptr, len).downgrade()
+}
+
 unsafe fn promotable_odd_drop(data: &mut AtomicPtr<()>, ptr: *const u8, len: usize) {
     data.with_mut(|shared| {
         let shared = *shared;
@@ -994,6 +1680,19 @@ unsafe fn promotable_odd_drop(data: &mut AtomicPtr<()>, ptr: *const u8, len: usi
     });
 }
 
+unsafe fn promotable_is_unique(data: &AtomicPtr<()>) -> bool {
+    let shared = data.load(Ordering::Acquire);
+    let kind = shared as usize & KIND_MASK;
+
+    if kind == KIND_ARC {
+        (*shared.cast::<Shared>()).is_unique()
+    } else {
+        // A `Bytes` that has never been cloned still holds the buffer as a
+        // boxed slice, so it is the only handle.
+        true
+    }
+}
+
 unsafe fn free_boxed_slice(buf: *mut u8, offset: *const u8, len: usize) {
     let cap = (offset as usize - buf as usize) + len;
     dealloc(buf, Layout::from_size_align(cap, 1).unwrap())
@@ -1002,15 +1701,23 @@ unsafe fn free_boxed_slice(buf: *mut u8, offset: *const u8, len: usize) {
 // ===== impl SharedVtable =====
 
 struct Shared {
-    // Holds arguments to dealloc upon Drop, but otherwise doesn't use them
+    // Holds arguments to dealloc once the last strong reference is released,
+    // but otherwise doesn't use them
     buf: *mut u8,
     cap: usize,
     ref_cnt: AtomicUsize,
+    // The number of `WeakBytes`, plus one held collectively by all strong
+    // references, as in `Arc`. The `Shared` itself is freed once it drops to
+    // zero.
+    weak_cnt: AtomicUsize,
 }
 
-impl Drop for Shared {
-    fn drop(&mut self) {
-        unsafe { dealloc(self.buf, Layout::from_size_align(self.cap, 1).unwrap()) }
+impl Shared {
+    fn is_unique(&self) -> bool {
+        // The `Acquire` ordering synchronizes with the `Release` as part of
+        // the `fetch_sub` in `release_shared`, so that once the other handles
+        // are gone their accesses happen before those of the caller.
+        self.ref_cnt.load(Ordering::Acquire) == 1
     }
 }
 
@@ -1023,6 +1730,10 @@ const _: [(); 0 - mem::align_of::<Shared>() % 2] = []; // Assert that the alignm
 static SHARED_VTABLE: Vtable = Vtable {
     clone: shared_clone,
     to_vec: shared_to_vec,
+    try_to_mut: shared_try_to_mut,
+    is_unique: shared_is_unique,
+    info: shared_info,
+    downgrade: shared_downgrade,
     drop: shared_drop,
 };
 
@@ -1050,8 +1761,8 @@ unsafe fn shared_to_vec_impl(shared: *mut Shared, ptr: *const u8, len: usize) ->
         let buf = (*shared).buf;
         let cap = (*shared).cap;
 
-        // Deallocate Shared
-        drop(Box::from_raw(shared as *mut mem::ManuallyDrop<Shared>));
+        // Release Shared, the buffer now belongs to the `Vec`
+        release_shared_weak(shared);
 
         // Copy back buffer
         ptr::copy(ptr, buf, len);
@@ -1068,6 +1779,66 @@ unsafe fn shared_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Vec
     shared_to_vec_impl(data.load(Ordering::Relaxed).cast(), ptr, len)
 }
 
+unsafe fn shared_try_to_mut_impl(
+    shared: *mut Shared,
+    ptr: *const u8,
+    len: usize,
+) -> Option<BytesMut> {
+    // The same uniqueness check as in `shared_to_vec_impl`.
+    if (*shared)
+        .ref_cnt
+        .compare_exchange(1, 0, Ordering::AcqRel, Ordering::Relaxed)
+        .is_ok()
+    {
+        let buf = (*shared).buf;
+        let cap = (*shared).cap;
+
+        // Release Shared, the buffer now belongs to the `BytesMut`
+        release_shared_weak(shared);
+
+        // The whole buffer was initialized when it was promoted from a boxed
+        // slice, so hand it over as is and keep the view at the same offset.
+        let off = ptr as usize - buf as usize;
+
+        let mut b = BytesMut::from_vec(Vec::from_raw_parts(buf, off + len, cap));
+        b.set_start(off);
+        Some(b)
+    } else {
+        None
+    }
+}
+
+unsafe fn shared_try_to_mut(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Option<BytesMut> {
+    shared_try_to_mut_impl(data.load(Ordering::Relaxed).cast(), ptr, len)
+}
+
+unsafe fn shared_is_unique(data: &AtomicPtr<()>) -> bool {
+    let shared = data.load(Ordering::Relaxed);
+    (*shared.cast::<Shared>()).is_unique()
+}
+
+unsafe fn shared_info_impl(shared: *mut Shared) -> BytesInfo {
+    let ref_count = (*shared).ref_cnt.load(Ordering::Relaxed);
+    BytesInfo::shared(ref_count, (*shared).cap)
+}
+
+unsafe fn shared_info(data: &AtomicPtr<()>, _ptr: *const u8, _len: usize) -> BytesInfo {
+    shared_info_impl(data.load(Ordering::Relaxed).cast())
+}
+
+unsafe fn shared_downgrade(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    let shared = data.load(Ordering::Relaxed);
+    let old_size = (*shared.cast::<Shared>())
+        .weak_cnt
+        .fetch_add(1, Ordering::Relaxed);
+
+    if old_size > usize::MAX >> 1 {
+        crate::abort();
+    }
+
+    WeakBytes::with_vtable(ptr, len, shared, &SHARED_WEAK_VTABLE)
+}
+
 unsafe fn shared_drop(data: &mut AtomicPtr<()>, _ptr: *const u8, _len: usize) {
     data.with_mut(|shared| {
         release_shared(shared.cast());
@@ -1115,6 +1886,7 @@ unsafe fn shallow_clone_vec(
         // for the new clone that will be returned from
         // `shallow_clone`.
         ref_cnt: AtomicUsize::new(2),
+        weak_cnt: AtomicUsize::new(1),
     });
 
     let shared = Box::into_raw(shared);
@@ -1151,8 +1923,7 @@ unsafe fn shallow_clone_vec(
             // The upgrade failed, a concurrent clone happened. Release
             // the allocation that was made in this thread, it will not
             // be needed.
-            let shared = Box::from_raw(shared);
-            mem::forget(*shared);
+            drop(Box::from_raw(shared));
 
             // Buffer already promoted to shared storage, so increment ref
             // count.
@@ -1189,10 +1960,249 @@ unsafe fn release_shared(ptr: *mut Shared) {
     // instead.
     (*ptr).ref_cnt.load(Ordering::Acquire);
 
-    // Drop the data
+    // Drop the data, then the reference held by the strong handles on the
+    // `Shared` itself.
+    dealloc((*ptr).buf, Layout::from_size_align((*ptr).cap, 1).unwrap());
+    release_shared_weak(ptr);
+}
+
+unsafe fn release_shared_weak(ptr: *mut Shared) {
+    // Same protocol as `release_shared`.
+    if (*ptr).weak_cnt.fetch_sub(1, Ordering::Release) != 1 {
+        return;
+    }
+
+    (*ptr).weak_cnt.load(Ordering::Acquire);
+
     drop(Box::from_raw(ptr));
 }
 
+static SHARED_WEAK_VTABLE: WeakVtable = WeakVtable {
+    upgrade: shared_upgrade,
+    clone: shared_weak_clone,
+    drop: shared_weak_drop,
+};
+
+unsafe fn shared_upgrade(data: *mut (), ptr: *const u8, len: usize) -> Option<Bytes> {
+    let shared = data as *mut Shared;
+
+    // Only increment the count if the buffer is still alive, as
+    // `Weak::upgrade` does.
+    let mut cur = (*shared).ref_cnt.load(Ordering::Relaxed);
+
+    loop {
+        if cur == 0 {
+            return None;
+        }
+
+        if cur > usize::MAX >> 1 {
+            crate::abort();
+        }
+
+        match (*shared).ref_cnt.compare_exchange_weak(
+            cur,
+            cur + 1,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                return Some(Bytes {
+                    ptr,
+                    len,
+                    data: AtomicPtr::new(data),
+                    vtable: &SHARED_VTABLE,
+                })
+            }
+            Err(actual) => cur = actual,
+        }
+    }
+}
+
+unsafe fn shared_weak_clone(data: *mut ()) {
+    let old_size = (*(data as *mut Shared))
+        .weak_cnt
+        .fetch_add(1, Ordering::Relaxed);
+
+    if old_size > usize::MAX >> 1 {
+        crate::abort();
+    }
+}
+
+unsafe fn shared_weak_drop(data: *mut ()) {
+    release_shared_weak(data as *mut Shared);
+}
+
+// ===== impl OwnedVtable =====
+
+// The type-erased header shared by every `Owned<T>`. `Owned` is `repr(C)` so
+// that a pointer to it can be used as a pointer to its `lifetime` field.
+#[repr(C)]
+struct OwnedLifetime {
+    ref_cnt: AtomicUsize,
+    // The number of `WeakBytes`, plus one held collectively by all strong
+    // references, as in `Arc`.
+    weak_cnt: AtomicUsize,
+    // Drops the owner in place, once the last strong reference is released.
+    drop: unsafe fn(*mut ()),
+    // Frees the allocation, once the last weak reference is released.
+    dealloc: unsafe fn(*mut ()),
+}
+
+#[repr(C)]
+struct Owned<T> {
+    lifetime: OwnedLifetime,
+    owner: T,
+}
+
+unsafe fn owned_drop_in_place<T>(ptr: *mut ()) {
+    ptr::drop_in_place(&mut (*(ptr as *mut Owned<T>)).owner);
+}
+
+unsafe fn owned_dealloc<T>(ptr: *mut ()) {
+    // The owner has already been dropped in place.
+    drop(Box::from_raw(ptr as *mut Owned<mem::ManuallyDrop<T>>));
+}
+
+static OWNED_VTABLE: Vtable = Vtable {
+    clone: owned_clone,
+    to_vec: owned_to_vec,
+    try_to_mut: owned_try_to_mut,
+    is_unique: owned_is_unique,
+    info: owned_info,
+    downgrade: owned_downgrade,
+    drop: owned_drop,
+};
+
+unsafe fn owned_clone(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Bytes {
+    let owned = data.load(Ordering::Relaxed);
+    let lifetime = owned as *const OwnedLifetime;
+    let old_size = (*lifetime).ref_cnt.fetch_add(1, Ordering::Relaxed);
+
+    if old_size > usize::MAX >> 1 {
+        crate::abort();
+    }
+
+    Bytes {
+        ptr,
+        len,
+        data: AtomicPtr::new(owned),
+        vtable: &OWNED_VTABLE,
+    }
+}
+
+unsafe fn owned_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Vec<u8> {
+    let v = slice::from_raw_parts(ptr, len).to_vec();
+    release_owned(data.load(Ordering::Relaxed));
+    v
+}
+
+unsafe fn owned_try_to_mut(_: &AtomicPtr<()>, _: *const u8, _: usize) -> Option<BytesMut> {
+    // The owner's memory cannot be reused as a `BytesMut`.
+    None
+}
+
+unsafe fn owned_is_unique(_: &AtomicPtr<()>) -> bool {
+    // The owner's memory cannot be reused as a `BytesMut`.
+    false
+}
+
+unsafe fn owned_info(data: &AtomicPtr<()>, _ptr: *const u8, _len: usize) -> BytesInfo {
+    let lifetime = data.load(Ordering::Relaxed) as *const OwnedLifetime;
+
+    BytesInfo {
+        kind: Kind::Owned,
+        ref_count: Some((*lifetime).ref_cnt.load(Ordering::Relaxed)),
+        capacity: None,
+    }
+}
+
+unsafe fn owned_downgrade(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    let owned = data.load(Ordering::Relaxed);
+    owned_weak_clone(owned);
+    WeakBytes::with_vtable(ptr, len, owned, &OWNED_WEAK_VTABLE)
+}
+
+unsafe fn owned_drop(data: &mut AtomicPtr<()>, _ptr: *const u8, _len: usize) {
+    data.with_mut(|owned| {
+        release_owned(*owned);
+    });
+}
+
+unsafe fn release_owned(owned: *mut ()) {
+    let lifetime = owned as *const OwnedLifetime;
+
+    // Same protocol as `release_shared`.
+    if (*lifetime).ref_cnt.fetch_sub(1, Ordering::Release) != 1 {
+        return;
+    }
+
+    (*lifetime).ref_cnt.load(Ordering::Acquire);
+
+    ((*lifetime).drop)(owned);
+    release_owned_weak(owned);
+}
+
+unsafe fn release_owned_weak(owned: *mut ()) {
+    let lifetime = owned as *const OwnedLifetime;
+
+    if (*lifetime).weak_cnt.fetch_sub(1, Ordering::Release) != 1 {
+        return;
+    }
+
+    (*lifetime).weak_cnt.load(Ordering::Acquire);
+
+    ((*lifetime).dealloc)(owned);
+}
+
+static OWNED_WEAK_VTABLE: WeakVtable = WeakVtable {
+    upgrade: owned_upgrade,
+    clone: owned_weak_clone,
+    drop: release_owned_weak,
+};
+
+unsafe fn owned_upgrade(data: *mut (), ptr: *const u8, len: usize) -> Option<Bytes> {
+    let lifetime = data as *const OwnedLifetime;
+
+    // See `shared_upgrade`.
+    let mut cur = (*lifetime).ref_cnt.load(Ordering::Relaxed);
+
+    loop {
+        if cur == 0 {
+            return None;
+        }
+
+        if cur > usize::MAX >> 1 {
+            crate::abort();
+        }
+
+        match (*lifetime).ref_cnt.compare_exchange_weak(
+            cur,
+            cur + 1,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                return Some(Bytes {
+                    ptr,
+                    len,
+                    data: AtomicPtr::new(data),
+                    vtable: &OWNED_VTABLE,
+                })
+            }
+            Err(actual) => cur = actual,
+        }
+    }
+}
+
+unsafe fn owned_weak_clone(data: *mut ()) {
+    let lifetime = data as *const OwnedLifetime;
+    let old_size = (*lifetime).weak_cnt.fetch_add(1, Ordering::Relaxed);
+
+    if old_size > usize::MAX >> 1 {
+        crate::abort();
+    }
+}
+
 // Ideally we would always use this version of `ptr_map` since it is strict
 // provenance compatible, but it results in worse codegen. We will however still
 // use it on miri because it gives better diagnostics for people who test bytes
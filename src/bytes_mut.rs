@@ -1,6 +1,8 @@
+use core::alloc::Layout;
 use core::iter::{FromIterator, Iterator};
+use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
-use core::ops::{Deref, DerefMut};
+use core::ops::{self, Deref, DerefMut};
 use core::ptr::{self, NonNull};
 use core::{cmp, fmt, hash, isize, slice, usize};
 
@@ -12,12 +14,15 @@ use alloc::{
     vec::Vec,
 };
 
-use crate::buf::{IntoIter, UninitSlice};
-use crate::bytes::Vtable;
+use crate::buf::{search, IntoIter, UninitSlice};
+use crate::bytes::{BytesInfo, Vtable, WeakBytes, WeakVtable};
 #[allow(unused)]
 use crate::loom::sync::atomic::AtomicMut;
-use crate::loom::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use crate::{Buf, BufMut, Bytes};
+use crate::loom::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use crate::{Buf, BufMut, Bytes, TryReserveError};
+
+#[cfg(feature = "std")]
+use std::io;
 
 /// A unique reference to a contiguous slice of memory.
 ///
@@ -65,6 +70,79 @@ pub struct BytesMut {
     data: *mut Shared,
 }
 
+/// Space reserved in a `BytesMut` by [`put_placeholder`], to be filled in once
+/// its value is known.
+///
+/// A placeholder records its offset from the start of the `BytesMut` it was
+/// created by, along with how many bytes had been removed from the front of
+/// that `BytesMut` at the time. It survives reallocations but must only be
+/// used with that `BytesMut`.
+///
+/// Bytes removed from the front with `split_to` or `advance` are accounted
+/// for, also when `reserve` moves the data afterwards. Filling the
+/// placeholder panics once it has been removed itself or no longer lies
+/// within the buffer.
+///
+/// [`put_placeholder`]: struct.BytesMut.html#method.put_placeholder
+#[derive(Debug)]
+#[must_use = "placeholders should be filled in"]
+pub struct Placeholder<T> {
+    // Offset from the start of the `BytesMut` when it was created.
+    pos: usize,
+    // Bytes removed from the front of the `BytesMut` at that time, see
+    // `BytesMut::front_position`.
+    front: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Integer types that space can be reserved for with [`put_placeholder`].
+///
+/// This trait is sealed and implemented for `u8`, `u16`, `u32` and `u64`,
+/// which have matching `fill_*` methods.
+///
+/// ```compile_fail
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// let placeholder = buf.put_placeholder::<String>();
+/// ```
+///
+/// [`put_placeholder`]: struct.BytesMut.html#method.put_placeholder
+pub trait PlaceholderInt: sealed::Sealed {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! placeholder_int {
+    ($($t:ty)*) => {$(
+        impl sealed::Sealed for $t {}
+        impl PlaceholderInt for $t {}
+    )*};
+}
+
+placeholder_int!(u8 u16 u32 u64);
+
+impl<T> Placeholder<T> {
+    /// Returns the offset of the placeholder from the start of the buffer at
+    /// the time it was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::from(&b"abc"[..]);
+    /// let placeholder = buf.put_placeholder::<u16>();
+    ///
+    /// assert_eq!(3, placeholder.position());
+    /// # buf.fill_u16_be(placeholder, 0);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
 // Thread-safe reference-counted container for the shared storage. This mostly
 // the same as `core::sync::Arc` but without the weak counter. The ref counting
 // fns are based on the ones found in `std`.
@@ -78,6 +156,17 @@ struct Shared {
     vec: Vec<u8>,
     original_capacity_repr: usize,
     ref_count: AtomicUsize,
+    // The number of `WeakBytes`, plus one held collectively by all strong
+    // references, as in `Arc`. It is set to `usize::MAX` while
+    // `Shared::is_unique` runs, so that no weak reference can be created in
+    // the meantime.
+    weak_count: AtomicUsize,
+    // Set once a placeholder was put in a buffer sharing this. From then on,
+    // `reserve` keeps the buffer shared so that `front_base` is maintained.
+    tracks_front: AtomicBool,
+    // The number of bytes removed from the front of the buffer before the
+    // start of `vec`. Only updated through a unique handle.
+    front_base: usize,
 }
 
 // Buffer storage strategy flags.
@@ -143,6 +232,29 @@ impl BytesMut {
         BytesMut::from_vec(Vec::with_capacity(capacity))
     }
 
+    /// Tries to create a new `BytesMut` with the specified capacity.
+    ///
+    /// This is the fallible counterpart of [`with_capacity`]: an error is
+    /// returned instead of aborting if the capacity overflows or the
+    /// allocator fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BytesMut, TryReserveError};
+    ///
+    /// let bytes = BytesMut::try_with_capacity(64).unwrap();
+    /// assert!(bytes.capacity() >= 64);
+    ///
+    /// let err = BytesMut::try_with_capacity(usize::MAX).unwrap_err();
+    /// assert_eq!(err, TryReserveError::CapacityOverflow);
+    /// ```
+    ///
+    /// [`with_capacity`]: #method.with_capacity
+    pub fn try_with_capacity(capacity: usize) -> Result<BytesMut, TryReserveError> {
+        Ok(BytesMut::from_vec(vec_with_capacity(capacity, true)?))
+    }
+
     /// Creates a new `BytesMut` with default capacity.
     ///
     /// Resulting object has length 0 and unspecified capacity.
@@ -391,6 +503,43 @@ impl BytesMut {
         }
     }
 
+    /// Splits off the bytes up to the first occurrence of `delimiter`.
+    ///
+    /// If `delimiter` is found, the bytes preceding it are returned and
+    /// `self` is advanced past the delimiter, which is dropped. Otherwise
+    /// `None` is returned and `self` is left unchanged, so that more data can
+    /// be appended before trying again.
+    ///
+    /// This is an `O(1)` operation on top of the search, that just increases
+    /// the reference count and sets a few indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::from(&b"GET / HTTP/1.1\r\nHo"[..]);
+    ///
+    /// assert_eq!(buf.split_to_delimiter(b"\r\n").unwrap(), &b"GET / HTTP/1.1"[..]);
+    /// assert_eq!(buf.split_to_delimiter(b"\r\n"), None);
+    ///
+    /// buf.extend_from_slice(b"st: a\r\n");
+    /// assert_eq!(buf.split_to_delimiter(b"\r\n").unwrap(), &b"Host: a"[..]);
+    /// assert!(buf.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` is empty.
+    pub fn split_to_delimiter(&mut self, delimiter: &[u8]) -> Option<BytesMut> {
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+
+        let at = search::find(self, delimiter)?;
+        let ret = self.split_to(at);
+        self.advance(delimiter.len());
+        Some(ret)
+    }
+
     /// Shortens the buffer, keeping the first `len` bytes and dropping the
     /// rest.
     ///
@@ -573,12 +722,104 @@ impl BytesMut {
             return;
         }
 
-        self.reserve_inner(additional);
+        if let Err(err) = self.reserve_inner(additional, true, false) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more bytes to be
+    /// inserted into the given `BytesMut`.
+    ///
+    /// This works the same way as [`reserve`], including reusing the space of
+    /// a buffer that isn't shared anymore, but returns an error instead of
+    /// panicking or aborting when the capacity overflows or the allocator
+    /// fails. The `BytesMut` is left unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BytesMut, TryReserveError};
+    ///
+    /// let mut buf = BytesMut::from(&b"hello"[..]);
+    /// buf.try_reserve(64).unwrap();
+    /// assert!(buf.capacity() >= 69);
+    ///
+    /// assert_eq!(buf.try_reserve(usize::MAX), Err(TryReserveError::CapacityOverflow));
+    /// assert_eq!(buf, &b"hello"[..]);
+    /// ```
+    ///
+    /// [`reserve`]: #method.reserve
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let len = self.len();
+        let rem = self.capacity() - len;
+
+        if additional <= rem {
+            return Ok(());
+        }
+
+        self.reserve_inner(additional, true, true).map(|_| ())
+    }
+
+    /// Attempts to make room for at least `additional` more bytes without
+    /// allocating.
+    ///
+    /// Returns `true` if `additional` bytes fit in the current capacity, or
+    /// if room could be made by reclaiming the space in front of the data
+    /// that was already consumed, for instance with [`advance`] or
+    /// [`split_to`]. The data is moved to the start of the buffer in that
+    /// case, subject to the same conditions as in [`reserve`].
+    ///
+    /// Reclaiming only works when `self` is the sole owner of the underlying
+    /// buffer. Returns `false`, leaving `self` unchanged, if other `Bytes` or
+    /// `BytesMut` handles still point into it or if the buffer is too small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::with_capacity(64);
+    /// buf.extend_from_slice(&[0; 48]);
+    /// let frozen = buf.split_to(40).freeze();
+    ///
+    /// // `frozen` still holds the front of the buffer
+    /// assert!(!buf.try_reclaim(48));
+    ///
+    /// drop(frozen);
+    /// assert!(buf.try_reclaim(48));
+    /// assert!(buf.capacity() >= 56);
+    ///
+    /// // more than the buffer holds
+    /// assert!(!buf.try_reclaim(64));
+    /// ```
+    ///
+    /// [`advance`]: #method.advance
+    /// [`split_to`]: #method.split_to
+    /// [`reserve`]: #method.reserve
+    #[must_use = "consider BytesMut::reserve if you need an infallible reservation"]
+    pub fn try_reclaim(&mut self, additional: usize) -> bool {
+        let len = self.len();
+        let rem = self.capacity() - len;
+
+        if additional <= rem {
+            return true;
+        }
+
+        self.reserve_inner(additional, false, false) == Ok(true)
     }
 
     // In separate function to allow the short-circuits in `reserve` to
     // be inline-able. Significant helps performance.
-    fn reserve_inner(&mut self, additional: usize) {
+    //
+    // Returns `false` instead of allocating when `allocate` isn't set.
+    // Allocation failures abort unless `fallible` is set, and capacity
+    // overflows are always returned.
+    fn reserve_inner(
+        &mut self,
+        additional: usize,
+        allocate: bool,
+        fallible: bool,
+    ) -> Result<bool, TryReserveError> {
         let len = self.len();
         let kind = self.kind();
 
@@ -623,11 +864,15 @@ impl BytesMut {
                     // can gain capacity back.
                     self.cap += off;
                 } else {
+                    if !allocate {
+                        return Ok(false);
+                    }
+
                     // Not enough space, or reusing might be too much overhead:
                     // allocate more space!
                     let mut v =
                         ManuallyDrop::new(rebuild_vec(self.ptr.as_ptr(), self.len, self.cap, off));
-                    v.reserve(additional);
+                    vec_reserve(&mut v, additional, fallible)?;
 
                     // Update the info
                     self.ptr = vptr(v.as_mut_ptr().add(off));
@@ -635,7 +880,7 @@ impl BytesMut {
                     self.cap = v.capacity() - off;
                 }
 
-                return;
+                return Ok(true);
             }
         }
 
@@ -646,7 +891,9 @@ impl BytesMut {
         // allocating a new vector with the requested capacity.
         //
         // Compute the new capacity
-        let mut new_cap = len.checked_add(additional).expect("overflow");
+        let mut new_cap = len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
 
         let original_capacity;
         let original_capacity_repr;
@@ -668,9 +915,15 @@ impl BytesMut {
 
                 let offset = offset_from(self.ptr.as_ptr(), ptr);
 
+                // The end of the reservation, relative to the start of the
+                // vector, must not overflow either.
+                let new_end = new_cap
+                    .checked_add(offset)
+                    .ok_or(TryReserveError::CapacityOverflow)?;
+
                 // Compare the condition in the `kind == KIND_VEC` case above
                 // for more details.
-                if v_capacity >= new_cap + offset {
+                if v_capacity >= new_end {
                     self.cap = new_cap;
                     // no copy is necessary
                 } else if v_capacity >= new_cap && offset >= len {
@@ -682,7 +935,12 @@ impl BytesMut {
 
                     self.ptr = vptr(ptr);
                     self.cap = v.capacity();
+                    (*shared).front_base = (*shared).front_base.wrapping_add(offset);
                 } else {
+                    if !allocate {
+                        return Ok(false);
+                    }
+
                     // calculate offset
                     let off = (self.ptr.as_ptr() as usize) - (v.as_ptr() as usize);
 
@@ -690,7 +948,9 @@ impl BytesMut {
                     // `Vec`, so it does not take the offset into account.
                     //
                     // Thus we have to manually add it here.
-                    new_cap = new_cap.checked_add(off).expect("overflow");
+                    new_cap = new_cap
+                        .checked_add(off)
+                        .ok_or(TryReserveError::CapacityOverflow)?;
 
                     // The vector capacity is not sufficient. The reserve request is
                     // asking for more than the initial buffer capacity. Allocate more
@@ -714,25 +974,35 @@ impl BytesMut {
                     // care about in the unused capacity before calling `reserve`.
                     debug_assert!(off + len <= v.capacity());
                     v.set_len(off + len);
-                    v.reserve(new_cap - v.len());
+                    vec_reserve(v, new_cap - v.len(), fallible)?;
 
                     // Update the info
                     self.ptr = vptr(v.as_mut_ptr().add(off));
                     self.cap = v.capacity() - off;
                 }
 
-                return;
+                return Ok(true);
             } else {
+                if !allocate {
+                    return Ok(false);
+                }
+
                 new_cap = cmp::max(new_cap, original_capacity);
             }
         }
 
         // Create a new vector to store the data
-        let mut v = ManuallyDrop::new(Vec::with_capacity(new_cap));
+        let mut v = ManuallyDrop::new(vec_with_capacity(new_cap, fallible)?);
 
         // Copy the bytes
         v.extend_from_slice(self.as_ref());
 
+        let front = if self.tracks_front() {
+            Some(self.front_position())
+        } else {
+            None
+        };
+
         // Release the shared handle. This must be done *after* the bytes are
         // copied.
         unsafe { release_shared(shared) };
@@ -743,6 +1013,18 @@ impl BytesMut {
         self.ptr = vptr(v.as_mut_ptr());
         self.len = v.len();
         self.cap = v.capacity();
+
+        if let Some(front) = front {
+            // Placeholders are located through `front_base`, so the buffer
+            // has to stay shared.
+            unsafe {
+                self.promote_to_shared(/*ref_count = */ 1);
+                (*self.data).front_base = front;
+                (*self.data).tracks_front.store(true, Ordering::Relaxed);
+            }
+        }
+
+        Ok(true)
     }
 
     /// Appends given bytes to this `BytesMut`.
@@ -778,6 +1060,30 @@ impl BytesMut {
         }
     }
 
+    /// Tries to append given bytes to this `BytesMut`.
+    ///
+    /// This is the fallible counterpart of [`extend_from_slice`]: if the
+    /// capacity cannot be reserved, an error is returned and `self` is left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::new();
+    /// buf.try_extend_from_slice(b"aaabbb").unwrap();
+    ///
+    /// assert_eq!(b"aaabbb", &buf[..]);
+    /// ```
+    ///
+    /// [`extend_from_slice`]: #method.extend_from_slice
+    pub fn try_extend_from_slice(&mut self, extend: &[u8]) -> Result<(), TryReserveError> {
+        self.try_reserve(extend.len())?;
+        self.extend_from_slice(extend);
+        Ok(())
+    }
+
     /// Absorbs a `BytesMut` that was previously split off.
     ///
     /// If the two `BytesMut` objects were previously contiguous and not mutated
@@ -813,6 +1119,302 @@ impl BytesMut {
         }
     }
 
+    /// Appends zeroed space for a `T` to be filled in later, and returns a
+    /// handle to it.
+    ///
+    /// This is meant for length prefixes and other headers whose value is
+    /// only known once the rest of the frame has been written. The handle
+    /// records the offset of the placeholder from the start of the buffer, so
+    /// it stays valid when `reserve` moves the data to a new allocation.
+    ///
+    /// To keep track of the bytes removed from its front, the buffer is
+    /// moved to shared storage, as `split_to` does, by the first placeholder.
+    ///
+    /// `T` is one of `u8`, `u16`, `u32` or `u64`, for which the `fill_*`
+    /// methods are provided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, BytesMut};
+    ///
+    /// let mut buf = BytesMut::with_capacity(4);
+    /// let len = buf.put_placeholder::<u32>();
+    /// buf.put_slice(b"hello world");
+    ///
+    /// let n = buf.len_since(&len) as u32;
+    /// buf.fill_u32_be(len, n);
+    ///
+    /// assert_eq!(&buf[..], b"\x00\x00\x00\x0bhello world");
+    /// ```
+    pub fn put_placeholder<T: PlaceholderInt>(&mut self) -> Placeholder<T> {
+        let pos = self.len();
+        self.put_bytes(0, mem::size_of::<T>());
+        self.track_front();
+        Placeholder {
+            pos,
+            front: self.front_position(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes written after `placeholder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn len_since<T>(&self, placeholder: &Placeholder<T>) -> usize {
+        self.len() - self.placeholder_range(placeholder).end
+    }
+
+    /// Fills `placeholder` with `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u8(&mut self, placeholder: Placeholder<u8>, n: u8) {
+        self.fill(placeholder, &[n]);
+    }
+
+    /// Fills `placeholder` with `n` in big-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u16_be(&mut self, placeholder: Placeholder<u16>, n: u16) {
+        self.fill(placeholder, &n.to_be_bytes());
+    }
+
+    /// Fills `placeholder` with `n` in little-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u16_le(&mut self, placeholder: Placeholder<u16>, n: u16) {
+        self.fill(placeholder, &n.to_le_bytes());
+    }
+
+    /// Fills `placeholder` with `n` in big-endian byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{BufMut, BytesMut};
+    ///
+    /// let mut buf = BytesMut::new();
+    /// let checksum = buf.put_placeholder::<u32>();
+    /// buf.put_u8(0xff);
+    /// buf.fill_u32_be(checksum, 0x0102_0304);
+    ///
+    /// assert_eq!(&buf[..], b"\x01\x02\x03\x04\xff");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u32_be(&mut self, placeholder: Placeholder<u32>, n: u32) {
+        self.fill(placeholder, &n.to_be_bytes());
+    }
+
+    /// Fills `placeholder` with `n` in little-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u32_le(&mut self, placeholder: Placeholder<u32>, n: u32) {
+        self.fill(placeholder, &n.to_le_bytes());
+    }
+
+    /// Fills `placeholder` with `n` in big-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u64_be(&mut self, placeholder: Placeholder<u64>, n: u64) {
+        self.fill(placeholder, &n.to_be_bytes());
+    }
+
+    /// Fills `placeholder` with `n` in little-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `placeholder` is no longer within the buffer, for example
+    /// because it was split off with `split_to`.
+    pub fn fill_u64_le(&mut self, placeholder: Placeholder<u64>, n: u64) {
+        self.fill(placeholder, &n.to_le_bytes());
+    }
+
+    /// Reads at most `max` bytes from `r` into the end of the buffer, with a
+    /// single call to `read`.
+    ///
+    /// The buffer is grown the same way as with [`reserve`] if it cannot hold
+    /// `max` more bytes. Returns the number of bytes read, where `0` means
+    /// that `r` reached its end or that `max` is `0`.
+    ///
+    /// Only the `max` bytes handed to `read` are zeroed beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::new();
+    /// let mut src = &b"hello world"[..];
+    ///
+    /// assert_eq!(buf.read_from(&mut src, 5).unwrap(), 5);
+    /// assert_eq!(buf, &b"hello"[..]);
+    /// assert_eq!(src, b" world");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Errors from `read` are returned as is, and nothing is appended.
+    ///
+    /// [`reserve`]: #method.reserve
+    #[cfg(feature = "std")]
+    pub fn read_from<R: io::Read + ?Sized>(&mut self, r: &mut R, max: usize) -> io::Result<usize> {
+        self.reserve(max);
+
+        let dst = self.spare_init(0, max);
+        let n = r.read(dst)?;
+        assert!(n <= max, "`read` returned more bytes than requested");
+
+        unsafe { self.advance_mut(n) };
+        Ok(n)
+    }
+
+    /// Reads all bytes from `r` until its end, appending them to the buffer.
+    ///
+    /// The buffer is grown the same way as with [`reserve`] whenever it is
+    /// full. The spare capacity is only zeroed once before `read` sees it,
+    /// even across calls to `read` that do not fill it. Reads that fail with
+    /// `ErrorKind::Interrupted` are retried.
+    ///
+    /// Returns the number of bytes read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::from(&b"> "[..]);
+    ///
+    /// assert_eq!(buf.read_to_end_from(&mut &b"hello world"[..]).unwrap(), 11);
+    /// assert_eq!(buf, &b"> hello world"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Any other error from `read` is returned. The bytes read before the
+    /// error are left in the buffer.
+    ///
+    /// [`reserve`]: #method.reserve
+    #[cfg(feature = "std")]
+    pub fn read_to_end_from<R: io::Read + ?Sized>(&mut self, r: &mut R) -> io::Result<usize> {
+        let start = self.len();
+        // The number of bytes of spare capacity that are already initialized.
+        let mut initialized = 0;
+
+        loop {
+            if self.len() == self.capacity() {
+                // `initialized` is 0 here, so it stays valid wherever
+                // `reserve` moves the data.
+                self.reserve(64);
+            }
+
+            let cap = self.capacity() - self.len();
+            let dst = self.spare_init(initialized, cap);
+
+            match r.read(dst) {
+                Ok(0) => return Ok(self.len() - start),
+                Ok(n) => {
+                    assert!(n <= cap, "`read` returned more bytes than requested");
+                    initialized = cap - n;
+                    unsafe { self.advance_mut(n) };
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {
+                    initialized = cap;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads exactly `cnt` bytes from `r`, appending them to the buffer.
+    ///
+    /// The buffer is grown the same way as with [`reserve`] if it cannot hold
+    /// `cnt` more bytes. Reads that fail with `ErrorKind::Interrupted` are
+    /// retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf = BytesMut::new();
+    /// let mut src = &b"hello world"[..];
+    ///
+    /// buf.read_exact_from(&mut src, 5).unwrap();
+    /// assert_eq!(buf, &b"hello"[..]);
+    ///
+    /// let err = buf.read_exact_from(&mut src, 10).unwrap_err();
+    /// assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    /// assert_eq!(buf, &b"hello world"[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind `ErrorKind::UnexpectedEof` if `r` ends
+    /// before `cnt` bytes were read, or any other error from `read`. The
+    /// bytes read before the error are left in the buffer.
+    ///
+    /// [`reserve`]: #method.reserve
+    #[cfg(feature = "std")]
+    pub fn read_exact_from<R: io::Read + ?Sized>(
+        &mut self,
+        r: &mut R,
+        cnt: usize,
+    ) -> io::Result<()> {
+        self.reserve(cnt);
+
+        // The first `read` gets `cnt` zeroed bytes, the later ones get what
+        // is left of them.
+        let mut rem = cnt;
+        let mut initialized = 0;
+
+        while rem > 0 {
+            let dst = self.spare_init(initialized, rem);
+
+            match r.read(dst) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ));
+                }
+                Ok(n) => {
+                    assert!(n <= rem, "`read` returned more bytes than requested");
+                    rem -= n;
+                    initialized = rem;
+                    unsafe { self.advance_mut(n) };
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {
+                    initialized = rem;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
     // private
 
     // For now, use a `Vec` to manage the memory for us, but we may want to
@@ -839,6 +1441,75 @@ impl BytesMut {
         }
     }
 
+    /// Makes the buffer shared, and `reserve` keep it shared, so that
+    /// `front_position` is maintained.
+    fn track_front(&mut self) {
+        if self.kind() == KIND_VEC {
+            unsafe {
+                self.promote_to_shared(/*ref_count = */ 1);
+            }
+        }
+
+        let shared: *mut Shared = self.data;
+        unsafe { (*shared).tracks_front.store(true, Ordering::Relaxed) };
+    }
+
+    fn tracks_front(&self) -> bool {
+        if self.kind() == KIND_VEC {
+            return false;
+        }
+
+        let shared: *mut Shared = self.data;
+        unsafe { (*shared).tracks_front.load(Ordering::Relaxed) }
+    }
+
+    /// Returns the number of bytes removed from the front of the buffer since
+    /// it was made shared. Unlike the offset into the allocation, it is not
+    /// reset when `reserve` moves the data.
+    ///
+    /// Only meaningful once `track_front` was called.
+    fn front_position(&self) -> usize {
+        debug_assert!(self.tracks_front());
+
+        let shared: *mut Shared = self.data;
+        unsafe {
+            let off = offset_from(self.ptr.as_ptr(), (*shared).vec.as_ptr() as *mut u8);
+            (*shared).front_base.wrapping_add(off)
+        }
+    }
+
+    fn placeholder_range<T>(&self, placeholder: &Placeholder<T>) -> ops::Range<usize> {
+        assert!(
+            self.tracks_front(),
+            "placeholder does not belong to this buffer"
+        );
+
+        // the number of bytes removed from the front since `placeholder` was
+        // created
+        let removed = self.front_position().wrapping_sub(placeholder.front);
+        assert!(
+            removed <= placeholder.pos,
+            "placeholder was split off: {:?} <= {:?}",
+            removed,
+            placeholder.pos,
+        );
+
+        let start = placeholder.pos - removed;
+        let end = start + mem::size_of::<T>();
+        assert!(
+            end <= self.len(),
+            "placeholder out of bounds: {:?} <= {:?}",
+            end,
+            self.len(),
+        );
+        start..end
+    }
+
+    fn fill<T>(&mut self, placeholder: Placeholder<T>, src: &[u8]) {
+        let range = self.placeholder_range(&placeholder);
+        self[range].copy_from_slice(src);
+    }
+
     #[inline]
     fn as_slice(&self) -> &[u8] {
         unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
@@ -849,7 +1520,7 @@ impl BytesMut {
         unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
     }
 
-    unsafe fn set_start(&mut self, start: usize) {
+    pub(crate) unsafe fn set_start(&mut self, start: usize) {
         // Setting the start to 0 is a no-op, so return early if this is the
         // case.
         if start == 0 {
@@ -948,6 +1619,9 @@ impl BytesMut {
             vec: rebuild_vec(self.ptr.as_ptr(), self.len, self.cap, off),
             original_capacity_repr,
             ref_count: AtomicUsize::new(ref_cnt),
+            weak_count: AtomicUsize::new(1),
+            tracks_front: AtomicBool::new(false),
+            front_base: 0,
         });
 
         let shared = Box::into_raw(shared);
@@ -992,6 +1666,22 @@ impl BytesMut {
         self.data = invalid_ptr((pos << VEC_POS_OFFSET) | (prev & NOT_VEC_POS_MASK));
     }
 
+    /// Returns the first `len` bytes of spare capacity as an initialized
+    /// slice, zeroing all but the first `initialized` of them.
+    #[cfg(feature = "std")]
+    fn spare_init(&mut self, initialized: usize, len: usize) -> &mut [u8] {
+        let dst = self.uninit_slice();
+        assert!(len <= dst.len());
+
+        unsafe {
+            let ptr = dst.as_mut_ptr();
+            if initialized < len {
+                ptr::write_bytes(ptr.add(initialized), 0, len - initialized);
+            }
+            slice::from_raw_parts_mut(ptr, len)
+        }
+    }
+
     #[inline]
     fn uninit_slice(&mut self) -> &mut UninitSlice {
         unsafe {
@@ -1340,7 +2030,20 @@ unsafe fn release_shared(ptr: *mut Shared) {
     // instead.
     (*ptr).ref_count.load(Ordering::Acquire);
 
-    // Drop the data
+    // Drop the data, then the reference held by the strong handles on the
+    // `Shared` itself.
+    drop(mem::replace(&mut (*ptr).vec, Vec::new()));
+    release_shared_weak(ptr);
+}
+
+unsafe fn release_shared_weak(ptr: *mut Shared) {
+    // Same protocol as `release_shared`.
+    if (*ptr).weak_count.fetch_sub(1, Ordering::Release) != 1 {
+        return;
+    }
+
+    (*ptr).weak_count.load(Ordering::Acquire);
+
     drop(Box::from_raw(ptr));
 }
 
@@ -1348,15 +2051,68 @@ impl Shared {
     fn is_unique(&self) -> bool {
         // The goal is to check if the current handle is the only handle
         // that currently has access to the buffer. This is done by
-        // checking if the `ref_count` is currently 1.
+        // checking if the `ref_count` is currently 1, and that there are
+        // no weak references that could be upgraded later on.
         //
+        // As in `Arc::is_unique`, the weak count is locked while the
+        // `ref_count` is checked, so that no weak reference can be created
+        // from another handle in between. If the lock is acquired, there
+        // are no weak references, so none can be upgraded either.
+        if self
+            .weak_count
+            .compare_exchange(1, usize::MAX, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+
         // The `Acquire` ordering synchronizes with the `Release` as
         // part of the `fetch_sub` in `release_shared`. The `fetch_sub`
         // operation guarantees that any mutations done in other threads
         // are ordered before the `ref_count` is decremented. As such,
         // this `Acquire` will guarantee that those mutations are
         // visible to the current thread.
-        self.ref_count.load(Ordering::Acquire) == 1
+        let unique = self.ref_count.load(Ordering::Acquire) == 1;
+
+        self.weak_count.store(1, Ordering::Release);
+
+        unique
+    }
+
+    /// Takes over the buffer if the caller holds the only strong reference.
+    ///
+    /// On success the reference count is 0, so any remaining weak references
+    /// can no longer be upgraded, and the caller is responsible for the
+    /// returned `Shared`, which has no weak references.
+    unsafe fn try_take(ptr: *mut Shared) -> Option<*mut Shared> {
+        if (*ptr)
+            .ref_count
+            .compare_exchange(1, 0, Ordering::AcqRel, Ordering::Relaxed)
+            .is_err()
+        {
+            return None;
+        }
+
+        // Weak references can only be created from strong ones, so if there
+        // are none left, there won't be any in the future either.
+        if (*ptr).weak_count.load(Ordering::Acquire) == 1 {
+            return Some(ptr);
+        }
+
+        // Detach the buffer from the weak references by moving it into a
+        // new `Shared`.
+        let shared = Box::new(Shared {
+            vec: mem::replace(&mut (*ptr).vec, Vec::new()),
+            original_capacity_repr: (*ptr).original_capacity_repr,
+            ref_count: AtomicUsize::new(0),
+            weak_count: AtomicUsize::new(1),
+            tracks_front: AtomicBool::new((*ptr).tracks_front.load(Ordering::Relaxed)),
+            front_base: (*ptr).front_base,
+        });
+
+        release_shared_weak(ptr);
+
+        Some(Box::into_raw(shared))
     }
 }
 
@@ -1671,11 +2427,92 @@ unsafe fn rebuild_vec(ptr: *mut u8, mut len: usize, mut cap: usize, off: usize)
     Vec::from_raw_parts(ptr, len, cap)
 }
 
+/// `Vec::with_capacity`, returning an error instead of aborting when
+/// `fallible` is set.
+fn vec_with_capacity(cap: usize, fallible: bool) -> Result<Vec<u8>, TryReserveError> {
+    if !fallible {
+        return Ok(Vec::with_capacity(cap));
+    }
+
+    if cap == 0 {
+        return Ok(Vec::new());
+    }
+    if cap > isize::MAX as usize {
+        return Err(TryReserveError::CapacityOverflow);
+    }
+
+    let layout = Layout::from_size_align(cap, 1).map_err(|_| TryReserveError::CapacityOverflow)?;
+    let ptr = unsafe { alloc::alloc::alloc(layout) };
+    if ptr.is_null() {
+        return Err(TryReserveError::AllocError { layout });
+    }
+
+    Ok(unsafe { Vec::from_raw_parts(ptr, 0, cap) })
+}
+
+/// `Vec::reserve`, returning an error instead of aborting when `fallible` is
+/// set. `vec` is left untouched on error.
+///
+/// `Vec::try_reserve` is only stable since 1.57, so the allocation is done
+/// by hand with the same amortized growth.
+fn vec_reserve(
+    vec: &mut Vec<u8>,
+    additional: usize,
+    fallible: bool,
+) -> Result<(), TryReserveError> {
+    if !fallible {
+        vec.reserve(additional);
+        return Ok(());
+    }
+
+    let len = vec.len();
+    let cap = vec.capacity();
+    if cap - len >= additional {
+        return Ok(());
+    }
+
+    let required = len
+        .checked_add(additional)
+        .ok_or(TryReserveError::CapacityOverflow)?;
+    if required > isize::MAX as usize {
+        return Err(TryReserveError::CapacityOverflow);
+    }
+    let new_cap = cmp::max(
+        cmp::min(cap.saturating_mul(2), isize::MAX as usize),
+        required,
+    );
+    let new_cap = cmp::max(new_cap, 8);
+
+    if cap == 0 {
+        *vec = vec_with_capacity(new_cap, true)?;
+        return Ok(());
+    }
+
+    let new_layout =
+        Layout::from_size_align(new_cap, 1).map_err(|_| TryReserveError::CapacityOverflow)?;
+    unsafe {
+        let old_layout = Layout::from_size_align_unchecked(cap, 1);
+        let ptr = alloc::alloc::realloc(vec.as_mut_ptr(), old_layout, new_cap);
+        if ptr.is_null() {
+            return Err(TryReserveError::AllocError { layout: new_layout });
+        }
+
+        // the old buffer now belongs to the new `Vec`
+        ptr::write(vec, Vec::from_raw_parts(ptr, len, new_cap));
+    }
+
+    Ok(())
+}
+
 // ===== impl SharedVtable =====
 
 static SHARED_VTABLE: Vtable = Vtable {
     clone: shared_v_clone,
     to_vec: shared_v_to_vec,
+    try_to_mut: shared_v_try_to_mut,
+    is_unique: shared_v_is_unique,
+    info: shared_v_info,
+    downgrade: shared_v_downgrade,
     drop: shared_v_drop,
 };
 
@@ -1690,12 +2527,10 @@ unsafe fn shared_v_clone(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> By
 unsafe fn shared_v_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> Vec<u8> {
     let shared: *mut Shared = data.load(Ordering::Relaxed).cast();
 
-    if (*shared).is_unique() {
-        let shared = &mut *shared;
-
+    if let Some(shared) = Shared::try_take(shared) {
         // Drop shared
-        let mut vec = mem::replace(&mut shared.vec, Vec::new());
-        release_shared(shared);
+        let mut vec = mem::replace(&mut (*shared).vec, Vec::new());
+        drop(Box::from_raw(shared));
 
         // Copy back buffer
         ptr::copy(ptr, vec.as_mut_ptr(), len);
@@ -1709,12 +2544,130 @@ unsafe fn shared_v_to_vec(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> V
     }
 }
 
+unsafe fn shared_v_try_to_mut(
+    data: &AtomicPtr<()>,
+    ptr: *const u8,
+    len: usize,
+) -> Option<BytesMut> {
+    let shared: *mut Shared = data.load(Ordering::Relaxed).cast();
+
+    Shared::try_take(shared).map(|shared| {
+        // The handle becomes the only reference to `Shared` again, and
+        // takes back all of the capacity from its start to the end of the
+        // vector.
+        (*shared).ref_count.store(1, Ordering::Relaxed);
+
+        let v = &mut (*shared).vec;
+        let off = offset_from(ptr as *mut u8, v.as_mut_ptr());
+        let cap = v.capacity() - off;
+
+        BytesMut {
+            ptr: vptr(ptr as *mut u8),
+            len,
+            cap,
+            data: shared,
+        }
+    })
+}
+
+unsafe fn shared_v_is_unique(data: &AtomicPtr<()>) -> bool {
+    // Weak references are not counted here, `shared_v_try_to_mut` detaches the
+    // buffer from them.
+    let shared = data.load(Ordering::Relaxed) as *mut Shared;
+    (*shared).ref_count.load(Ordering::Acquire) == 1
+}
+
+unsafe fn shared_v_info(data: &AtomicPtr<()>, _ptr: *const u8, _len: usize) -> BytesInfo {
+    let shared = data.load(Ordering::Relaxed) as *mut Shared;
+    let ref_count = (*shared).ref_count.load(Ordering::Relaxed);
+    BytesInfo::shared(ref_count, (*shared).vec.capacity())
+}
+
+unsafe fn shared_v_downgrade(data: &AtomicPtr<()>, ptr: *const u8, len: usize) -> WeakBytes {
+    let shared = data.load(Ordering::Relaxed) as *mut Shared;
+    let weak_count = &(*shared).weak_count;
+
+    let mut cur = weak_count.load(Ordering::Relaxed);
+
+    loop {
+        // The weak count is locked by `Shared::is_unique`, wait until it is
+        // released.
+        if cur == usize::MAX {
+            cur = weak_count.load(Ordering::Relaxed);
+            continue;
+        }
+
+        if cur > isize::MAX as usize {
+            crate::abort();
+        }
+
+        match weak_count.compare_exchange_weak(cur, cur + 1, Ordering::Acquire, Ordering::Relaxed) {
+            Ok(_) => break,
+            Err(actual) => cur = actual,
+        }
+    }
+
+    WeakBytes::with_vtable(ptr, len, shared.cast(), &SHARED_WEAK_VTABLE)
+}
+
 unsafe fn shared_v_drop(data: &mut AtomicPtr<()>, _ptr: *const u8, _len: usize) {
     data.with_mut(|shared| {
         release_shared(*shared as *mut Shared);
     });
 }
 
+static SHARED_WEAK_VTABLE: WeakVtable = WeakVtable {
+    upgrade: shared_v_upgrade,
+    clone: shared_v_weak_clone,
+    drop: shared_v_weak_drop,
+};
+
+unsafe fn shared_v_upgrade(data: *mut (), ptr: *const u8, len: usize) -> Option<Bytes> {
+    let shared = data as *mut Shared;
+
+    // Only increment the count if the buffer is still alive, as
+    // `Weak::upgrade` does.
+    let mut cur = (*shared).ref_count.load(Ordering::Relaxed);
+
+    loop {
+        if cur == 0 {
+            return None;
+        }
+
+        if cur > isize::MAX as usize {
+            crate::abort();
+        }
+
+        match (*shared).ref_count.compare_exchange_weak(
+            cur,
+            cur + 1,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => {
+                let data = AtomicPtr::new(data);
+                return Some(Bytes::with_vtable(ptr, len, data, &SHARED_VTABLE));
+            }
+            Err(actual) => cur = actual,
+        }
+    }
+}
+
+unsafe fn shared_v_weak_clone(data: *mut ()) {
+    // A weak reference exists, so the weak count cannot be locked.
+    let old_size = (*(data as *mut Shared))
+        .weak_count
+        .fetch_add(1, Ordering::Relaxed);
+
+    if old_size > isize::MAX as usize {
+        crate::abort();
+    }
+}
+
+unsafe fn shared_v_weak_drop(data: *mut ()) {
+    release_shared_weak(data as *mut Shared);
+}
+
 // compile-fails
 
 /// ```compile_fail
@@ -0,0 +1,452 @@
+use core::ops::{Bound, RangeBounds};
+use core::{cmp, fmt};
+
+use alloc::sync::Arc;
+
+use crate::{Buf, Bytes};
+
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+/// A byte sequence stored as a balanced tree of `Bytes` leaves.
+///
+/// `BytesRope` is meant for large content that is edited in place: inserting,
+/// removing and slicing only rebuild the `O(log n)` nodes along the edited
+/// paths, and never copy the bytes themselves. Leaves are `Bytes` slices that
+/// share storage with the `Bytes` they were created from.
+///
+/// Nodes are reference counted, so cloning a `BytesRope` is cheap and the
+/// clone is unaffected by later edits to the original.
+///
+/// The content is read back through the [`Buf`] implementation, which yields
+/// one leaf per chunk.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::{Buf, Bytes, BytesRope};
+///
+/// let mut rope = BytesRope::from(Bytes::from_static(b"hello world"));
+/// rope.insert(5, Bytes::from_static(b","));
+/// rope.remove(6..7);
+/// rope.insert(6, Bytes::from_static(b"\n"));
+///
+/// assert_eq!(rope.len(), 12);
+/// assert_eq!(rope.copy_to_bytes(12), &b"hello,\nworld"[..]);
+/// ```
+///
+/// [`Buf`]: trait.Buf.html
+#[derive(Clone, Default)]
+pub struct BytesRope {
+    root: Option<Arc<Node>>,
+}
+
+// An AVL tree holding the bytes in its leaves. Leaves are never empty, and
+// the heights of the children of a branch differ by at most one.
+enum Node {
+    Leaf(Bytes),
+    Branch {
+        left: Arc<Node>,
+        right: Arc<Node>,
+        len: usize,
+        height: usize,
+    },
+}
+
+impl BytesRope {
+    /// Creates a new empty `BytesRope`.
+    ///
+    /// This function does not allocate.
+    #[inline]
+    pub fn new() -> BytesRope {
+        BytesRope { root: None }
+    }
+
+    /// Returns the number of bytes contained in this `BytesRope`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |node| node.len())
+    }
+
+    /// Returns true if the `BytesRope` has a length of 0.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts `bytes` at byte offset `at`.
+    ///
+    /// `bytes` becomes a leaf of the rope without being copied. This
+    /// operation is `O(log n)` in the number of leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, BytesRope};
+    ///
+    /// let mut rope = BytesRope::from(Bytes::from_static(b"ac"));
+    /// rope.insert(1, Bytes::from_static(b"b"));
+    ///
+    /// assert_eq!(rope.copy_to_bytes(3), &b"abc"[..]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn insert(&mut self, at: usize, bytes: Bytes) {
+        assert!(
+            at <= self.len(),
+            "insert index out of bounds: {:?} <= {:?}",
+            at,
+            self.len(),
+        );
+
+        if bytes.is_empty() {
+            return;
+        }
+
+        let (left, right) = split(self.root.take(), at);
+        let left = join(left, Some(Arc::new(Node::Leaf(bytes))));
+        self.root = join(left, right);
+    }
+
+    /// Removes the given range of bytes from the rope.
+    ///
+    /// This operation is `O(log n)` in the number of leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, BytesRope};
+    ///
+    /// let mut rope = BytesRope::from(Bytes::from_static(b"hello world"));
+    /// rope.remove(..6);
+    ///
+    /// assert_eq!(rope.copy_to_bytes(5), &b"world"[..]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Requires that `begin <= end` and `end <= self.len()`, otherwise
+    /// removing will panic.
+    pub fn remove(&mut self, range: impl RangeBounds<usize>) {
+        let (begin, end) = self.bounds(range);
+
+        let (left, rest) = split(self.root.take(), begin);
+        let (_, right) = split(rest, end - begin);
+        self.root = join(left, right);
+    }
+
+    /// Returns a slice of self for the provided range.
+    ///
+    /// The returned rope shares its leaves and most of its nodes with `self`.
+    /// This operation is `O(log n)` in the number of leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, BytesRope};
+    ///
+    /// let mut rope = BytesRope::from(Bytes::from_static(b"hello"));
+    /// rope.insert(5, Bytes::from_static(b" world"));
+    ///
+    /// let mut slice = rope.slice(3..8);
+    /// assert_eq!(slice.copy_to_bytes(5), &b"lo wo"[..]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Requires that `begin <= end` and `end <= self.len()`, otherwise
+    /// slicing will panic.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> BytesRope {
+        let (begin, end) = self.bounds(range);
+
+        let (_, rest) = split(self.root.clone(), begin);
+        let (root, _) = split(rest, end - begin);
+        BytesRope { root }
+    }
+
+    /// Appends the content of `other` to the end of `self`.
+    ///
+    /// This operation is `O(log n)` in the number of leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::{Buf, Bytes, BytesRope};
+    ///
+    /// let a = BytesRope::from(Bytes::from_static(b"hello "));
+    /// let b = BytesRope::from(Bytes::from_static(b"world"));
+    ///
+    /// let mut rope = a.concat(b);
+    /// assert_eq!(rope.copy_to_bytes(11), &b"hello world"[..]);
+    /// ```
+    pub fn concat(self, other: BytesRope) -> BytesRope {
+        BytesRope {
+            root: join(self.root, other.root),
+        }
+    }
+
+    fn bounds(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let len = self.len();
+
+        let begin = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("out of range"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+
+        assert!(
+            begin <= end,
+            "range start must not be greater than end: {:?} <= {:?}",
+            begin,
+            end,
+        );
+        assert!(
+            end <= len,
+            "range end out of bounds: {:?} <= {:?}",
+            end,
+            len,
+        );
+
+        (begin, end)
+    }
+
+    fn first_leaf(&self) -> Option<&Bytes> {
+        let mut node = self.root.as_ref()?;
+        loop {
+            match &**node {
+                Node::Leaf(bytes) => return Some(bytes),
+                Node::Branch { left, .. } => node = left,
+            }
+        }
+    }
+}
+
+impl Node {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(bytes) => bytes.len(),
+            Node::Branch { len, .. } => *len,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 0,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    fn children(&self) -> (Arc<Node>, Arc<Node>) {
+        match self {
+            Node::Branch { left, right, .. } => (left.clone(), right.clone()),
+            Node::Leaf(_) => unreachable!("leaf has no children"),
+        }
+    }
+
+    /// Calls `f` with each leaf in order until it returns `false`. Returns
+    /// `false` if the walk was stopped.
+    fn for_each_leaf(&self, f: &mut dyn FnMut(&Bytes) -> bool) -> bool {
+        match self {
+            Node::Leaf(bytes) => f(bytes),
+            Node::Branch { left, right, .. } => left.for_each_leaf(f) && right.for_each_leaf(f),
+        }
+    }
+}
+
+/// Creates a branch without rebalancing. The heights of `left` and `right`
+/// must differ by at most one.
+fn branch(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    debug_assert!(
+        cmp::max(left.height(), right.height()) - cmp::min(left.height(), right.height()) <= 1
+    );
+
+    Arc::new(Node::Branch {
+        len: left.len() + right.len(),
+        height: cmp::max(left.height(), right.height()) + 1,
+        left,
+        right,
+    })
+}
+
+/// Creates a branch of `left` and `right`, whose heights may differ by at
+/// most two, rotating once or twice if needed.
+fn balance(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    if right.height() > left.height() + 1 {
+        let (rl, rr) = right.children();
+        if rl.height() > rr.height() {
+            let (rll, rlr) = rl.children();
+            branch(branch(left, rll), branch(rlr, rr))
+        } else {
+            branch(branch(left, rl), rr)
+        }
+    } else if left.height() > right.height() + 1 {
+        let (ll, lr) = left.children();
+        if lr.height() > ll.height() {
+            let (lrl, lrr) = lr.children();
+            branch(branch(ll, lrl), branch(lrr, right))
+        } else {
+            branch(ll, branch(lr, right))
+        }
+    } else {
+        branch(left, right)
+    }
+}
+
+/// Concatenates two trees, descending along the edge of the taller one.
+fn join_nodes(left: Arc<Node>, right: Arc<Node>) -> Arc<Node> {
+    if left.height() > right.height() + 1 {
+        let (ll, lr) = left.children();
+        balance(ll, join_nodes(lr, right))
+    } else if right.height() > left.height() + 1 {
+        let (rl, rr) = right.children();
+        balance(join_nodes(left, rl), rr)
+    } else {
+        branch(left, right)
+    }
+}
+
+fn join(left: Option<Arc<Node>>, right: Option<Arc<Node>>) -> Option<Arc<Node>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(join_nodes(left, right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
+
+/// Splits a tree into the bytes before `at` and the bytes from `at` on.
+fn split(node: Option<Arc<Node>>, at: usize) -> (Option<Arc<Node>>, Option<Arc<Node>>) {
+    let node = match node {
+        Some(node) => node,
+        None => return (None, None),
+    };
+
+    if at == 0 {
+        return (None, Some(node));
+    }
+    if at >= node.len() {
+        return (Some(node), None);
+    }
+
+    match &*node {
+        Node::Leaf(bytes) => (
+            Some(Arc::new(Node::Leaf(bytes.slice(..at)))),
+            Some(Arc::new(Node::Leaf(bytes.slice(at..)))),
+        ),
+        Node::Branch { left, right, .. } => {
+            let left_len = left.len();
+            if at <= left_len {
+                let (a, b) = split(Some(left.clone()), at);
+                (a, join(b, Some(right.clone())))
+            } else {
+                let (a, b) = split(Some(right.clone()), at - left_len);
+                (join(Some(left.clone()), a), b)
+            }
+        }
+    }
+}
+
+impl Buf for BytesRope {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        match self.first_leaf() {
+            Some(bytes) => bytes,
+            None => &[],
+        }
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len(),
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.len(),
+        );
+
+        self.root = split(self.root.take(), cnt).1;
+    }
+
+    #[cfg(feature = "std")]
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        fn fill<'a>(node: &'a Node, dst: &mut [IoSlice<'a>], n: &mut usize) {
+            if *n == dst.len() {
+                return;
+            }
+            match node {
+                Node::Leaf(bytes) => {
+                    dst[*n] = IoSlice::new(bytes);
+                    *n += 1;
+                }
+                Node::Branch { left, right, .. } => {
+                    fill(left, dst, n);
+                    fill(right, dst, n);
+                }
+            }
+        }
+
+        let mut n = 0;
+        if let Some(root) = &self.root {
+            fill(root, dst, &mut n);
+        }
+        n
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[u8]) -> bool) {
+        if let Some(root) = &self.root {
+            root.for_each_leaf(&mut |bytes| f(bytes));
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        match self.first_leaf() {
+            // the request falls inside the first leaf
+            Some(bytes) if bytes.len() >= len => {
+                let ret = bytes.slice(..len);
+                self.advance(len);
+                ret
+            }
+            _ => {
+                assert!(len <= self.remaining(), "`len` greater than remaining");
+
+                let mut ret = crate::BytesMut::with_capacity(len);
+                crate::BufMut::put(&mut ret, self.take(len));
+                ret.freeze()
+            }
+        }
+    }
+}
+
+impl From<Bytes> for BytesRope {
+    fn from(bytes: Bytes) -> BytesRope {
+        if bytes.is_empty() {
+            BytesRope::new()
+        } else {
+            BytesRope {
+                root: Some(Arc::new(Node::Leaf(bytes))),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BytesRope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut leaves = f.debug_list();
+        if let Some(root) = &self.root {
+            root.for_each_leaf(&mut |bytes| {
+                leaves.entry(bytes);
+                true
+            });
+        }
+        leaves.finish()
+    }
+}
@@ -83,17 +83,143 @@ extern crate std;
 pub mod buf;
 pub use crate::buf::{Buf, BufMut};
 
+mod byte_str;
+mod byte_string;
 mod bytes;
 mod bytes_mut;
+mod bytes_rope;
 mod fmt;
 mod loom;
-pub use crate::bytes::Bytes;
-pub use crate::bytes_mut::BytesMut;
+mod split;
+pub use crate::byte_str::ByteStr;
+pub use crate::byte_string::ByteString;
+pub use crate::bytes::{Bytes, BytesInfo, WeakBytes};
+pub use crate::bytes_mut::{BytesMut, Placeholder, PlaceholderInt};
+pub use crate::bytes_rope::BytesRope;
+pub use crate::split::{ChunksExact, Split, SplitN, SplitWhitespace};
 
 // Optional Serde support
 #[cfg(feature = "serde")]
 mod serde;
 
+// Optional memory-mapped file support
+#[cfg(feature = "mmap")]
+mod mmap;
+
+/// Error type for the `try_get_` methods of [`Buf`].
+///
+/// Indicates that there were not enough remaining bytes in the buffer while
+/// attempting to read a value. The buffer is left unchanged when this error
+/// is returned.
+///
+/// [`Buf`]: trait.Buf.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryGetError {
+    /// The number of bytes necessary to get the value
+    pub requested: usize,
+
+    /// The number of bytes available in the buffer
+    pub available: usize,
+}
+
+impl core::fmt::Display for TryGetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "not enough bytes remaining in buffer to read value (requested {} but only {} available)",
+            self.requested, self.available
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryGetError {}
+
+#[cfg(feature = "std")]
+impl From<TryGetError> for std::io::Error {
+    fn from(error: TryGetError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::UnexpectedEof, error)
+    }
+}
+
+/// Error type for the `try_put_` methods of [`BufMut`].
+///
+/// Indicates that there was not enough remaining capacity in the buffer while
+/// attempting to write a value. Nothing is written when this error is
+/// returned.
+///
+/// [`BufMut`]: trait.BufMut.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryPutError {
+    /// The number of bytes necessary to put the value
+    pub requested: usize,
+
+    /// The remaining capacity of the buffer
+    pub available: usize,
+}
+
+impl core::fmt::Display for TryPutError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "not enough capacity remaining in buffer to write value (requested {} but only {} available)",
+            self.requested, self.available
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryPutError {}
+
+#[cfg(feature = "std")]
+impl From<TryPutError> for std::io::Error {
+    fn from(error: TryPutError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::WriteZero, error)
+    }
+}
+
+/// Error type for the fallible allocation methods, such as
+/// [`BytesMut::try_reserve`].
+///
+/// The buffer is left unchanged when this error is returned.
+///
+/// [`BytesMut::try_reserve`]: struct.BytesMut.html#method.try_reserve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum size of a buffer,
+    /// `isize::MAX` bytes.
+    CapacityOverflow,
+
+    /// The memory allocator returned an error.
+    AllocError {
+        /// The layout of the allocation request that failed.
+        layout: core::alloc::Layout,
+    },
+}
+
+impl core::fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            TryReserveError::CapacityOverflow => f.write_str(
+                "memory allocation failed because the computed capacity exceeded the maximum",
+            ),
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryReserveError {}
+
+#[cfg(feature = "std")]
+impl From<TryReserveError> for std::io::Error {
+    fn from(error: TryReserveError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::Other, error)
+    }
+}
+
 #[inline(never)]
 #[cold]
 fn abort() -> ! {
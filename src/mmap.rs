@@ -0,0 +1,51 @@
+use super::Bytes;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+
+impl Bytes {
+    /// Maps the contents of `file` into memory and returns them as `Bytes`.
+    ///
+    /// The file is mapped read-only. No data is copied: the returned handle,
+    /// and every handle obtained from it through `clone`, `slice`,
+    /// `split_to` or `split_off`, point into the same mapping. The file is
+    /// unmapped once the last of these handles is dropped.
+    ///
+    /// This function is only available with the `mmap` feature enabled.
+    ///
+    /// # Safety
+    ///
+    /// `Bytes` assumes that its contents never change. The caller must ensure
+    /// that the underlying file is neither modified nor truncated, by this or
+    /// any other process, for as long as any handle to the mapping is alive.
+    /// Modifying the file is undefined behavior, and truncating it may cause
+    /// the process to be killed when the missing pages are accessed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be mapped, for example because it
+    /// was not opened for reading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bytes::Bytes;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("segment.dat")?;
+    /// let mut data = unsafe { Bytes::map_file(&file)? };
+    ///
+    /// // Both halves still point into the mapping.
+    /// let header = data.split_to(16);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub unsafe fn map_file(file: &File) -> io::Result<Bytes> {
+        let mmap = Mmap::map(file)?;
+
+        if mmap.is_empty() {
+            return Ok(Bytes::new());
+        }
+
+        Ok(Bytes::from_owner(mmap))
+    }
+}
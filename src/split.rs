@@ -0,0 +1,225 @@
+use core::iter::FusedIterator;
+use core::{fmt, mem};
+
+use crate::{Buf, Bytes};
+
+/// An iterator over the sub-slices of a `Bytes` separated by elements that
+/// match a predicate.
+///
+/// This struct is created by [`Bytes::split`]. Each item is a `Bytes` sharing
+/// the storage of the original.
+///
+/// [`Bytes::split`]: struct.Bytes.html#method.split
+pub struct Split<F> {
+    bytes: Bytes,
+    pred: F,
+    finished: bool,
+}
+
+pub(crate) fn split<F>(bytes: Bytes, pred: F) -> Split<F> {
+    Split {
+        bytes,
+        pred,
+        finished: false,
+    }
+}
+
+impl<F> Split<F> {
+    fn finish(&mut self) -> Option<Bytes> {
+        if self.finished {
+            None
+        } else {
+            self.finished = true;
+            Some(mem::replace(&mut self.bytes, Bytes::new()))
+        }
+    }
+}
+
+impl<F> fmt::Debug for Split<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Split")
+            .field("bytes", &self.bytes)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<F> Iterator for Split<F>
+where
+    F: FnMut(&u8) -> bool,
+{
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.finished {
+            return None;
+        }
+
+        match self.bytes.iter().position(&mut self.pred) {
+            Some(at) => {
+                let ret = self.bytes.split_to(at);
+                self.bytes.advance(1);
+                Some(ret)
+            }
+            None => self.finish(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.bytes.len() + 1))
+        }
+    }
+}
+
+impl<F> FusedIterator for Split<F> where F: FnMut(&u8) -> bool {}
+
+/// An iterator over at most `n` sub-slices of a `Bytes` separated by elements
+/// that match a predicate.
+///
+/// This struct is created by [`Bytes::splitn`]. Each item is a `Bytes` sharing
+/// the storage of the original.
+///
+/// [`Bytes::splitn`]: struct.Bytes.html#method.splitn
+#[derive(Debug)]
+pub struct SplitN<F> {
+    inner: Split<F>,
+    count: usize,
+}
+
+pub(crate) fn splitn<F>(bytes: Bytes, n: usize, pred: F) -> SplitN<F> {
+    SplitN {
+        inner: split(bytes, pred),
+        count: n,
+    }
+}
+
+impl<F> Iterator for SplitN<F>
+where
+    F: FnMut(&u8) -> bool,
+{
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.inner.finish()
+            }
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        let upper = upper.map(|upper| upper.min(self.count));
+        (lower.min(self.count), upper)
+    }
+}
+
+impl<F> FusedIterator for SplitN<F> where F: FnMut(&u8) -> bool {}
+
+/// An iterator over the non-empty sub-slices of a `Bytes` separated by ASCII
+/// whitespace.
+///
+/// This struct is created by [`Bytes::split_whitespace`]. Each item is a
+/// `Bytes` sharing the storage of the original.
+///
+/// [`Bytes::split_whitespace`]: struct.Bytes.html#method.split_whitespace
+#[derive(Debug)]
+pub struct SplitWhitespace {
+    inner: Split<fn(&u8) -> bool>,
+}
+
+pub(crate) fn split_whitespace(bytes: Bytes) -> SplitWhitespace {
+    SplitWhitespace {
+        inner: split(bytes, u8::is_ascii_whitespace as fn(&u8) -> bool),
+    }
+}
+
+impl Iterator for SplitWhitespace {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        self.inner.by_ref().find(|bytes| !bytes.is_empty())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl FusedIterator for SplitWhitespace {}
+
+/// An iterator over a `Bytes` in chunks of `chunk_size` bytes, leaving out
+/// the last up to `chunk_size - 1` bytes.
+///
+/// This struct is created by [`Bytes::chunks_exact`]. Each item is a `Bytes`
+/// sharing the storage of the original. The left out bytes can be retrieved
+/// with [`remainder`].
+///
+/// [`Bytes::chunks_exact`]: struct.Bytes.html#method.chunks_exact
+/// [`remainder`]: #method.remainder
+#[derive(Debug)]
+pub struct ChunksExact {
+    bytes: Bytes,
+    rem: Bytes,
+    chunk_size: usize,
+}
+
+pub(crate) fn chunks_exact(mut bytes: Bytes, chunk_size: usize) -> ChunksExact {
+    assert!(chunk_size != 0, "chunk_size must not be zero");
+
+    let rem = bytes.split_off(bytes.len() - bytes.len() % chunk_size);
+    ChunksExact {
+        bytes,
+        rem,
+        chunk_size,
+    }
+}
+
+impl ChunksExact {
+    /// Returns the bytes left out by the iterator, which are fewer than
+    /// `chunk_size`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    ///
+    /// let bytes = Bytes::from_static(b"abcde");
+    /// let chunks = bytes.chunks_exact(2);
+    ///
+    /// assert_eq!(chunks.remainder(), &b"e"[..]);
+    /// ```
+    pub fn remainder(&self) -> &Bytes {
+        &self.rem
+    }
+}
+
+impl Iterator for ChunksExact {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        if self.bytes.is_empty() {
+            None
+        } else {
+            Some(self.bytes.split_to(self.chunk_size))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bytes.len() / self.chunk_size;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for ChunksExact {}
+
+impl FusedIterator for ChunksExact {}
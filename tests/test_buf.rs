@@ -1,6 +1,7 @@
 #![warn(rust_2018_idioms)]
 
-use bytes::Buf;
+use bytes::buf::VarintError;
+use bytes::{Buf, TryGetError};
 #[cfg(feature = "std")]
 use std::io::IoSlice;
 
@@ -72,6 +73,90 @@ fn test_vec_deque() {
     assert_eq!(b"world piece", &out[..]);
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_vec_deque_chunks_vectored() {
+    use std::collections::VecDeque;
+
+    let mut buffer: VecDeque<u8> = VecDeque::with_capacity(8);
+    buffer.extend(b"xxxxxxhe");
+    buffer.advance(6);
+    buffer.extend(b"llo");
+    assert_eq!(b"he", buffer.chunk());
+
+    let mut dst = [IoSlice::new(&[]); 3];
+    assert_eq!(2, buffer.chunks_vectored(&mut dst));
+    assert_eq!(&dst[0][..], b"he");
+    assert_eq!(&dst[1][..], b"llo");
+
+    let mut dst = [IoSlice::new(&[]); 1];
+    assert_eq!(1, buffer.chunks_vectored(&mut dst));
+    assert_eq!(&dst[0][..], b"he");
+
+    assert_eq!(0, VecDeque::new().chunks_vectored(&mut dst));
+}
+
+#[test]
+fn test_peek() {
+    let buf = &b"\x21\x54zomg"[..];
+    assert_eq!(Ok(0x21), buf.peek_u8());
+    assert_eq!(Ok(0x2154), buf.peek_u16());
+    assert_eq!(Ok(0x5421), buf.peek_i16_le());
+    assert_eq!(Ok(0x2154_7a6f), buf.peek_u32());
+    assert_eq!(6, buf.remaining());
+}
+
+#[test]
+#[should_panic(expected = "`nbytes` greater than 8")]
+fn test_peek_uint_too_wide() {
+    let buf = &[0; 16][..];
+    let _ = buf.peek_uint(9);
+}
+
+#[test]
+#[should_panic(expected = "`nbytes` greater than 8")]
+fn test_peek_int_le_too_wide() {
+    let buf = &[0; 16][..];
+    let _ = buf.peek_int_le(9);
+}
+
+#[test]
+fn test_peek_u32_buffer_underflow() {
+    let buf = &b"\x21\x54"[..];
+    assert_eq!(
+        Err(TryGetError {
+            requested: 4,
+            available: 2
+        }),
+        buf.peek_u32()
+    );
+    assert_eq!(
+        Err(TryGetError {
+            requested: 1,
+            available: 0
+        }),
+        (&b""[..]).peek_u8()
+    );
+}
+
+#[test]
+fn test_vec_deque_peek() {
+    use std::collections::VecDeque;
+
+    let mut buffer: VecDeque<u8> = VecDeque::with_capacity(8);
+    buffer.extend(b"xxxxxx\x01\x02");
+    buffer.advance(6);
+    buffer.extend(b"\x03\x04");
+    assert_eq!(&b"\x01\x02"[..], buffer.chunk());
+
+    assert_eq!(Ok(0x0102_0304), buffer.peek_u32());
+    assert_eq!(Ok(0x0403_0201), buffer.peek_u32_le());
+    let mut dst = [0; 3];
+    buffer.peek_slice(&mut dst).unwrap();
+    assert_eq!(dst, [1, 2, 3]);
+    assert_eq!(4, buffer.remaining());
+}
+
 #[test]
 fn test_deref_buf_forwards() {
     struct Special;
@@ -118,3 +203,522 @@ fn copy_to_bytes_overflow() {
 
     let _bytes = buf.copy_to_bytes(12);
 }
+
+#[test]
+fn test_try_get_u16() {
+    let mut buf = &b"\x21\x54zomg"[..];
+    assert_eq!(Ok(0x2154), buf.try_get_u16());
+    let mut buf = &b"\x21\x54zomg"[..];
+    assert_eq!(Ok(0x5421), buf.try_get_u16_le());
+}
+
+#[test]
+fn test_try_get_buffer_underflow() {
+    let mut buf = &b"\x21"[..];
+    assert_eq!(
+        Err(TryGetError {
+            requested: 2,
+            available: 1
+        }),
+        buf.try_get_u16()
+    );
+    assert_eq!(
+        Err(TryGetError {
+            requested: 8,
+            available: 1
+        }),
+        buf.try_get_f64_le()
+    );
+    assert_eq!(
+        Err(TryGetError {
+            requested: 3,
+            available: 1
+        }),
+        buf.try_get_uint(3)
+    );
+    assert_eq!(buf, &b"\x21"[..]);
+
+    assert_eq!(Ok(0x21), buf.try_get_u8());
+    assert_eq!(
+        Err(TryGetError {
+            requested: 1,
+            available: 0
+        }),
+        buf.try_get_i8()
+    );
+}
+
+#[test]
+fn test_try_get_across_chunks() {
+    let mut buf = (&b"\x01\x02"[..]).chain(&b"\x03\x04\x05"[..]);
+
+    assert_eq!(Ok(0x0102_0304), buf.try_get_u32());
+    assert_eq!(
+        Err(TryGetError {
+            requested: 2,
+            available: 1
+        }),
+        buf.try_get_i16_le()
+    );
+    assert_eq!(1, buf.remaining());
+}
+
+#[test]
+fn test_try_get_uint() {
+    let mut buf = &b"\x01\x02\x03\x04"[..];
+    assert_eq!(Ok(0x010203), buf.try_get_uint(3));
+    let mut buf = &b"\x01\x02\x03\x04"[..];
+    assert_eq!(Ok(0x030201), buf.try_get_int_le(3));
+}
+
+#[test]
+#[should_panic]
+fn test_try_get_uint_too_wide() {
+    let mut buf = &[0; 16][..];
+    let _ = buf.try_get_uint(9);
+}
+
+#[test]
+fn test_try_copy_to_slice() {
+    let mut buf = &b"hello world"[..];
+    let mut dst = [0; 12];
+
+    assert_eq!(
+        Err(TryGetError {
+            requested: 12,
+            available: 11
+        }),
+        buf.try_copy_to_slice(&mut dst)
+    );
+    assert_eq!([0; 12], dst);
+    assert_eq!(11, buf.remaining());
+
+    assert_eq!(Ok(()), buf.try_copy_to_slice(&mut dst[..11]));
+    assert_eq!(b"hello world", &dst[..11]);
+    assert!(!buf.has_remaining());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_try_get_error_into_io_error() {
+    let mut buf = &b""[..];
+    let err: std::io::Error = buf.try_get_u32().unwrap_err().into();
+    assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+}
+
+#[test]
+fn test_get_uvarint() {
+    let mut buf = &b"\x00\x01\x7f\x80\x01\xac\x02"[..];
+    assert_eq!(Ok(0), buf.get_uvarint_u64());
+    assert_eq!(Ok(1), buf.get_uvarint_u64());
+    assert_eq!(Ok(127), buf.get_uvarint_u32());
+    assert_eq!(Ok(128), buf.get_uvarint_u64());
+    assert_eq!(Ok(300), buf.get_uvarint_u32());
+    assert!(!buf.has_remaining());
+
+    let mut buf = &b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01"[..];
+    assert_eq!(Ok(core::u64::MAX), buf.get_uvarint_u64());
+}
+
+#[test]
+fn test_get_uvarint_errors() {
+    let mut buf = &b""[..];
+    assert_eq!(Err(VarintError::Truncated), buf.get_uvarint_u64());
+
+    let mut buf = &b"\xff\xff"[..];
+    assert_eq!(Err(VarintError::Truncated), buf.get_uvarint_u64());
+    assert_eq!(2, buf.remaining());
+
+    let mut buf = &b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..];
+    assert_eq!(Err(VarintError::Overflow), buf.get_uvarint_u64());
+    assert_eq!(10, buf.remaining());
+
+    let mut buf = &b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00"[..];
+    assert_eq!(Err(VarintError::Overflow), buf.get_uvarint_u64());
+
+    let mut buf = &b"\x80\x80\x80\x80\x80\x00"[..];
+    assert_eq!(Err(VarintError::Overflow), buf.get_ivarint_i32());
+    assert_eq!(6, buf.remaining());
+}
+
+#[test]
+fn test_get_ivarint() {
+    let mut buf = &b"\x00\x01\x02\x03\xfe\xff\xff\xff\x0f\xff\xff\xff\xff\x0f"[..];
+    assert_eq!(Ok(0), buf.get_ivarint_i64());
+    assert_eq!(Ok(-1), buf.get_ivarint_i64());
+    assert_eq!(Ok(1), buf.get_ivarint_i32());
+    assert_eq!(Ok(-2), buf.get_ivarint_i32());
+    assert_eq!(Ok(core::i32::MAX), buf.get_ivarint_i32());
+    assert_eq!(Ok(i64::from(core::i32::MIN)), buf.get_ivarint_i64());
+    assert!(!buf.has_remaining());
+}
+
+#[test]
+fn test_vec_deque_varint() {
+    use bytes::BufMut;
+    use std::collections::VecDeque;
+
+    let mut buffer: VecDeque<u8> = VecDeque::with_capacity(16);
+    buffer.extend(&[0; 12]);
+    buffer.advance(11);
+    let mut encoded = Vec::new();
+    encoded.put_uvarint(1 << 40);
+    encoded.put_ivarint(-300);
+    buffer.extend(&encoded);
+    assert!(buffer.chunk().len() < buffer.remaining());
+
+    assert_eq!(0, buffer.get_u8());
+    assert_eq!(Ok(1 << 40), buffer.get_uvarint_u64());
+    assert_eq!(Ok(-300), buffer.get_ivarint_i64());
+    assert!(!buffer.has_remaining());
+}
+
+#[test]
+fn test_get_prefixed_bytes() {
+    let mut buf = &b"\x03abc\x00\x02de\x01\x00\x00\x00f\x00"[..];
+    assert_eq!(&b"abc"[..], buf.get_u8_prefixed_bytes());
+    assert_eq!(&b"de"[..], buf.get_u16_prefixed_bytes());
+    assert_eq!(&b"f"[..], buf.get_u32_le_prefixed_bytes());
+    assert_eq!(&b""[..], buf.get_uvarint_prefixed_bytes());
+    assert!(!buf.has_remaining());
+}
+
+#[test]
+fn test_try_get_prefixed_bytes_truncated() {
+    let mut buf = &b"\x00\x00\x00"[..];
+    assert_eq!(
+        Err(TryGetError {
+            requested: 4,
+            available: 3
+        }),
+        buf.try_get_u32_prefixed_bytes()
+    );
+
+    let mut buf = &b"\xff\xff\xff\xff\xff\xff\xff\xffabc"[..];
+    assert_eq!(
+        Err(TryGetError {
+            requested: core::usize::MAX,
+            available: 11
+        }),
+        buf.try_get_u64_le_prefixed_bytes()
+    );
+    assert_eq!(11, buf.remaining());
+
+    let mut buf = &b"\x80"[..];
+    assert_eq!(
+        Err(VarintError::Truncated),
+        buf.try_get_uvarint_prefixed_bytes()
+    );
+    let mut buf = &b"\x02a"[..];
+    assert_eq!(
+        Err(VarintError::Truncated),
+        buf.try_get_uvarint_prefixed_bytes()
+    );
+    assert_eq!(2, buf.remaining());
+}
+
+#[test]
+#[should_panic]
+fn test_get_prefixed_bytes_underflow() {
+    let mut buf = &b"\x00\x05abc"[..];
+    buf.get_u16_prefixed_bytes();
+}
+
+#[test]
+fn test_find_byte_contiguous() {
+    let data: Vec<u8> = (0..100).collect();
+    for start in 0..8 {
+        let buf = &data[start..];
+        for &byte in &data {
+            let expected = buf.iter().position(|&b| b == byte);
+            assert_eq!(Ok(expected), buf.find_byte(byte));
+        }
+    }
+}
+
+#[test]
+fn test_find_slice_contiguous() {
+    let buf = &b"abcabd abd"[..];
+    assert_eq!(Ok(Some(3)), buf.find_slice(b"abd"));
+    assert_eq!(Ok(Some(0)), buf.find_slice(b""));
+    assert_eq!(Ok(Some(5)), buf.find_slice(b"d"));
+    assert_eq!(Ok(None), buf.find_slice(b"abe"));
+    assert_eq!(Ok(None), buf.find_slice(b"abcabd abd!"));
+}
+
+#[test]
+fn test_find_across_chunks() {
+    let buf = (&b"ab\r"[..])
+        .chain(&b""[..])
+        .chain(&b"\n"[..])
+        .chain(&b"\r"[..])
+        .chain(&b"\nxy"[..]);
+
+    assert_eq!(Ok(Some(2)), buf.find_byte(b'\r'));
+    assert_eq!(Ok(Some(3)), buf.find_byte(b'\n'));
+    assert_eq!(Ok(Some(6)), buf.find_byte(b'x'));
+    assert_eq!(Ok(Some(2)), buf.find_slice(b"\r\n"));
+    assert_eq!(Ok(Some(2)), buf.find_slice(b"\r\n\r\n"));
+    assert_eq!(Ok(Some(1)), buf.find_slice(b"b\r\n\r\nx"));
+    assert_eq!(Ok(Some(5)), buf.find_slice(b"\nxy"));
+    assert_eq!(Ok(None), buf.find_slice(b"\n\n"));
+    assert_eq!(Ok(None), buf.find_slice(b"xyz"));
+    assert_eq!(8, buf.remaining());
+}
+
+#[test]
+fn test_vec_deque_find() {
+    use std::collections::VecDeque;
+
+    let mut buffer: VecDeque<u8> = VecDeque::with_capacity(16);
+    buffer.extend(&[0; 12]);
+    buffer.advance(10);
+    buffer.extend(b"a\r\nbc");
+    assert!(buffer.chunk().len() < buffer.remaining());
+
+    assert_eq!(Ok(Some(3)), buffer.find_slice(b"\r\n"));
+    assert_eq!(Ok(Some(5)), buffer.find_byte(b'b'));
+}
+
+#[test]
+fn test_take_find() {
+    let buf = (&b"hello "[..]).chain(&b"world"[..]).take(8);
+
+    assert_eq!(Ok(Some(6)), buf.find_slice(b"wo"));
+    assert_eq!(Ok(None), buf.find_slice(b"wor"));
+    assert_eq!(Ok(None), buf.find_byte(b'r'));
+}
+
+#[test]
+fn test_lines() {
+    use bytes::BytesMut;
+
+    let mut lines = BytesMut::from(&b"one\n\ntwo\r\nth\rree\r"[..]).lines();
+    assert_eq!(lines.next().unwrap(), &b"one"[..]);
+    assert_eq!(lines.next().unwrap(), &b""[..]);
+    assert_eq!(lines.next().unwrap(), &b"two"[..]);
+    assert_eq!(lines.next(), None);
+    assert_eq!(lines.get_ref(), &b"th\rree\r"[..]);
+
+    lines.get_mut().extend_from_slice(b"\nfour");
+    assert_eq!(lines.next().unwrap(), &b"th\rree"[..]);
+    assert_eq!(lines.next(), None);
+    assert_eq!(lines.into_inner(), &b"four"[..]);
+}
+
+#[test]
+fn test_lines_across_chunks() {
+    let buf = (&b"ab\r"[..]).chain(&b"\ncd\n"[..]);
+    let lines: Vec<_> = buf.lines().collect();
+    assert_eq!(lines, [&b"ab"[..], &b"cd"[..]]);
+}
+
+#[test]
+fn test_lines_streaming() {
+    use bytes::{BufMut, BytesMut};
+
+    let mut lines = BytesMut::new().lines();
+    let mut got = Vec::new();
+    for &b in b"first line\r\nsecond\n\nx\n".iter() {
+        lines.get_mut().put_u8(b);
+        got.extend(lines.by_ref());
+    }
+
+    assert_eq!(got, [&b"first line"[..], b"second", b"", b"x"]);
+    assert!(lines.get_ref().is_empty());
+
+    // the bytes already searched span several chunks
+    let mut lines = (&b"ab"[..]).chain(BytesMut::new()).lines();
+    assert_eq!(lines.next(), None);
+    lines.get_mut().last_mut().put_u8(b'c');
+    assert_eq!(lines.next(), None);
+    lines.get_mut().last_mut().put_slice(b"\nd\n");
+    assert_eq!(lines.next().unwrap(), &b"abc"[..]);
+    assert_eq!(lines.next().unwrap(), &b"d"[..]);
+    assert_eq!(lines.next(), None);
+}
+
+/// A non-contiguous `Buf`, as another crate could write it, that only
+/// implements the required methods.
+struct TwoChunks {
+    a: &'static [u8],
+    b: &'static [u8],
+}
+
+impl Buf for TwoChunks {
+    fn remaining(&self) -> usize {
+        self.a.len() + self.b.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        if self.a.is_empty() {
+            self.b
+        } else {
+            self.a
+        }
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        let n = cnt.min(self.a.len());
+        self.a = &self.a[n..];
+        self.b = &self.b[cnt - n..];
+    }
+}
+
+/// The same, with its own `chunks_vectored`.
+#[cfg(feature = "std")]
+struct TwoChunksVectored(TwoChunks);
+
+#[cfg(feature = "std")]
+impl Buf for TwoChunksVectored {
+    fn remaining(&self) -> usize {
+        self.0.remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.0.chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.0.advance(cnt)
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let mut n = 0;
+        for (s, slot) in [self.0.a, self.0.b]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .zip(dst)
+        {
+            *slot = IoSlice::new(s);
+            n += 1;
+        }
+        n
+    }
+}
+
+#[test]
+fn test_lookahead_default_for_each_chunk() {
+    let buf = TwoChunks {
+        a: b"\x01\x02a\n",
+        b: b"b\x03\x04\n",
+    };
+
+    assert_eq!(Ok(1), buf.peek_u8());
+    assert_eq!(Ok(0x0102), buf.peek_u16());
+    // the second chunk cannot be reached through `&self`
+    assert_eq!(
+        Err(TryGetError {
+            requested: 6,
+            available: 4
+        }),
+        buf.peek_uint(6)
+    );
+    assert_eq!(Ok(Some(3)), buf.find_byte(b'\n'));
+    assert_eq!(Ok(Some(2)), buf.find_slice(b"a\n"));
+    // not found in the first chunk, but the second one was not searched
+    assert_eq!(
+        Err(TryGetError {
+            requested: 8,
+            available: 4
+        }),
+        buf.find_byte(b'b')
+    );
+    assert_eq!(
+        Err(TryGetError {
+            requested: 8,
+            available: 4
+        }),
+        buf.find_slice(b"\nb")
+    );
+
+    let mut lines = buf.lines();
+    assert_eq!(lines.next().unwrap(), &b"\x01\x02a"[..]);
+    assert_eq!(lines.next().unwrap(), &b"b\x03\x04"[..]);
+    assert_eq!(lines.next(), None);
+
+    let mut buf = TwoChunks {
+        a: b"\x80",
+        b: b"\x01",
+    };
+    assert_eq!(Err(VarintError::Truncated), buf.get_uvarint_u64());
+    assert_eq!(2, buf.remaining());
+    assert_eq!(0x8001, buf.get_u16());
+}
+
+#[test]
+#[should_panic(expected = "`for_each_chunk` does not reach all remaining bytes")]
+fn test_lines_default_for_each_chunk() {
+    let buf = TwoChunks {
+        a: b"ab",
+        b: b"c\n",
+    };
+    let _ = buf.lines().next();
+}
+
+/// A `Buf` made of one byte chunks, which only exposes them all through
+/// `chunks_vectored`.
+#[cfg(feature = "std")]
+struct Bytewise(&'static [u8]);
+
+#[cfg(feature = "std")]
+impl Buf for Bytewise {
+    fn remaining(&self) -> usize {
+        self.0.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.0[..std::cmp::min(1, self.0.len())]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.0 = &self.0[cnt..];
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let n = std::cmp::min(dst.len(), self.0.len());
+        for (i, slot) in dst[..n].iter_mut().enumerate() {
+            *slot = IoSlice::new(&self.0[i..i + 1]);
+        }
+        n
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lookahead_many_chunks_vectored() {
+    let buf = Bytewise(b"0123456789abcdefghijklmnopqrstuvwxyz\r\n");
+
+    assert_eq!(Ok(Some(36)), buf.find_slice(b"\r\n"));
+    assert_eq!(Ok(None), buf.find_byte(b'!'));
+
+    let mut dst = [0; 38];
+    buf.peek_slice(&mut dst).unwrap();
+    assert_eq!(&dst[..], &buf.0[..38]);
+
+    let mut lines = buf.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        &b"0123456789abcdefghijklmnopqrstuvwxyz"[..]
+    );
+    assert_eq!(lines.next(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lookahead_chunks_vectored() {
+    let buf = TwoChunksVectored(TwoChunks {
+        a: b"\x01\x02a\n",
+        b: b"b\x03\x04\n",
+    });
+
+    assert_eq!(Ok(0x0102_610a_6203), buf.peek_uint(6));
+    assert_eq!(Ok(Some(4)), buf.find_byte(b'b'));
+    assert_eq!(Ok(Some(3)), buf.find_slice(b"\nb"));
+
+    let mut buf = TwoChunksVectored(TwoChunks {
+        a: b"\x80",
+        b: b"\x01",
+    });
+    assert_eq!(Ok(128), buf.get_uvarint_u64());
+}
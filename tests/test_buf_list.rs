@@ -0,0 +1,117 @@
+#![warn(rust_2018_idioms)]
+
+use bytes::buf::BufList;
+use bytes::{Buf, Bytes};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+fn list(segments: &[&'static [u8]]) -> BufList {
+    segments.iter().map(|s| Bytes::from_static(s)).collect()
+}
+
+#[test]
+fn remaining_and_advance() {
+    let mut buf = list(&[b"hello", b" ", b"world"]);
+    assert_eq!(buf.remaining(), 11);
+    assert_eq!(buf.num_segments(), 3);
+    assert_eq!(buf.chunk(), b"hello");
+
+    buf.advance(3);
+    assert_eq!(buf.remaining(), 8);
+    assert_eq!(buf.chunk(), b"lo");
+
+    buf.advance(3);
+    assert_eq!(buf.num_segments(), 1);
+    assert_eq!(buf.chunk(), b"world");
+
+    buf.advance(5);
+    assert!(!buf.has_remaining());
+    assert_eq!(buf.chunk(), b"");
+    assert_eq!(buf.num_segments(), 0);
+}
+
+#[test]
+#[should_panic]
+fn advance_past_remaining() {
+    let mut buf = list(&[b"ab", b"cd"]);
+    buf.advance(5);
+}
+
+#[test]
+fn push_back_and_pop_front() {
+    let mut buf = BufList::new();
+    buf.push_back(Bytes::from_static(b"ab"));
+    buf.push_back(Bytes::new());
+    buf.push_back(Bytes::from_static(b"cd"));
+    assert_eq!(buf.num_segments(), 2);
+
+    assert_eq!(buf.get_u16(), 0x6162);
+    buf.push_back(Bytes::from_static(b"ef"));
+    assert_eq!(buf.pop_front().unwrap(), &b"cd"[..]);
+    assert_eq!(buf.remaining(), 2);
+    assert_eq!(buf.pop_front().unwrap(), &b"ef"[..]);
+    assert_eq!(buf.pop_front(), None);
+    assert_eq!(buf.remaining(), 0);
+}
+
+#[test]
+fn copy_to_bytes_within_segment_is_zero_copy() {
+    let first = Bytes::from(b"hello world".to_vec());
+    let ptr = first.as_ptr();
+
+    let mut buf = BufList::from(first);
+    buf.push_back(Bytes::from_static(b"!"));
+
+    let hello = buf.copy_to_bytes(5);
+    assert_eq!(hello, &b"hello"[..]);
+    assert_eq!(hello.as_ptr(), ptr);
+
+    let world = buf.copy_to_bytes(6);
+    assert_eq!(world, &b" world"[..]);
+    assert_eq!(world.as_ptr(), unsafe { ptr.add(5) });
+    assert_eq!(buf.num_segments(), 1);
+    assert_eq!(buf.remaining(), 1);
+}
+
+#[test]
+fn copy_to_bytes_across_segments() {
+    let mut buf = list(&[b"ab", b"cd", b"ef"]);
+    buf.advance(1);
+
+    assert_eq!(buf.copy_to_bytes(4), &b"bcde"[..]);
+    assert_eq!(buf.remaining(), 1);
+    assert_eq!(buf.chunk(), b"f");
+}
+
+#[test]
+#[should_panic]
+fn copy_to_bytes_overflow() {
+    let mut buf = list(&[b"ab", b"cd"]);
+    let _ = buf.copy_to_bytes(5);
+}
+
+#[test]
+fn lookahead_across_segments() {
+    let buf = list(&[b"GET / HT", b"TP/1.1\r", b"\nHost"]);
+
+    assert_eq!(buf.find_slice(b"\r\n"), Ok(Some(14)));
+    assert_eq!(buf.peek_u32(), Ok(0x4745_5420));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_read() {
+    let mut buf = list(&[b"hello", b" ", b"world"]);
+    buf.advance(2);
+
+    let mut iovecs = [IoSlice::new(&[]); 4];
+    assert_eq!(buf.chunks_vectored(&mut iovecs), 3);
+    assert_eq!(iovecs[0][..], b"llo"[..]);
+    assert_eq!(iovecs[1][..], b" "[..]);
+    assert_eq!(iovecs[2][..], b"world"[..]);
+    assert_eq!(iovecs[3][..], b""[..]);
+
+    let mut iovecs = [IoSlice::new(&[]); 2];
+    assert_eq!(buf.chunks_vectored(&mut iovecs), 2);
+    assert_eq!(iovecs[1][..], b" "[..]);
+}
@@ -1,7 +1,7 @@
 #![warn(rust_2018_idioms)]
 
 use bytes::buf::UninitSlice;
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, BytesMut, TryPutError};
 use core::fmt::Write;
 use core::usize;
 
@@ -176,3 +176,201 @@ fn copy_from_slice_panics_if_different_length_2() {
     let slice = unsafe { UninitSlice::from_raw_parts_mut(data.as_mut_ptr(), 3) };
     slice.copy_from_slice(b"abcd");
 }
+
+#[test]
+fn test_try_put_mut_slice() {
+    let mut v = [0, 0, 0, 0];
+    {
+        let mut s = &mut v[..];
+        assert_eq!(Ok(()), s.try_put_u16(0x0102));
+        assert_eq!(
+            Err(TryPutError {
+                requested: 4,
+                available: 2
+            }),
+            s.try_put_f32(1.2)
+        );
+        assert_eq!(
+            Err(TryPutError {
+                requested: 3,
+                available: 2
+            }),
+            s.try_put_slice(b"abc")
+        );
+        assert_eq!(
+            Err(TryPutError {
+                requested: 3,
+                available: 2
+            }),
+            s.try_put_bytes(b'x', 3)
+        );
+        assert_eq!(2, s.remaining_mut());
+        assert_eq!(Ok(()), s.try_put_uint_le(0x0304, 2));
+        assert_eq!(
+            Err(TryPutError {
+                requested: 1,
+                available: 0
+            }),
+            s.try_put_i8(-1)
+        );
+    }
+    assert_eq!(v, [1, 2, 4, 3]);
+}
+
+#[test]
+fn test_try_put_limit() {
+    let mut buf = Vec::new();
+    {
+        let mut dst = (&mut buf).limit(6);
+        assert_eq!(Ok(()), dst.try_put_u32_le(0x0403_0201));
+        assert_eq!(
+            Err(TryPutError {
+                requested: 8,
+                available: 2
+            }),
+            dst.try_put_u64(0)
+        );
+        assert_eq!(Ok(()), dst.try_put_bytes(0xff, 2));
+        assert!(!dst.has_remaining_mut());
+    }
+    assert_eq!(buf, [1, 2, 3, 4, 0xff, 0xff]);
+}
+
+#[test]
+fn test_try_put_vec_grows() {
+    let mut buf = Vec::new();
+    assert_eq!(Ok(()), buf.try_put_u128(1));
+    assert_eq!(Ok(()), buf.try_put_slice(b"hello"));
+    assert_eq!(buf.len(), 21);
+}
+
+#[test]
+#[should_panic]
+fn test_try_put_int_nbytes_overflow() {
+    let mut buf = Vec::new();
+    let _ = buf.try_put_int(0x1020304050607080, 9);
+}
+
+#[test]
+fn test_put_varint() {
+    let mut buf = Vec::new();
+    buf.put_uvarint(0);
+    buf.put_uvarint(300);
+    buf.put_uvarint_u32(127);
+    buf.put_ivarint(-1);
+    buf.put_ivarint_i32(1);
+    assert_eq!(buf, b"\x00\xac\x02\x7f\x01\x02");
+
+    let mut buf = Vec::new();
+    buf.put_uvarint(usize::MAX as u64);
+    buf.put_ivarint(core::i64::MIN);
+    assert_eq!(buf.len(), 20);
+}
+
+#[test]
+#[should_panic]
+fn test_put_varint_buffer_overflow() {
+    let mut v = [0; 2];
+    let mut s = &mut v[..];
+    s.put_uvarint(1 << 20);
+}
+
+#[test]
+fn test_put_fmt() {
+    let mut buf = BytesMut::new();
+    buf.put_fmt(format_args!("{}={:#x}", "len", 255)).unwrap();
+    write!(buf, ";{}", buf.len()).unwrap();
+    assert_eq!(buf, "len=0xff;8");
+}
+
+#[test]
+fn test_put_fmt_slice_overflow() {
+    let mut dst = [0u8; 6];
+    let mut buf = &mut dst[..];
+
+    let (a, b) = (123, 4567);
+    assert_eq!(
+        buf.put_fmt(format_args!("{}-{}", a, b)),
+        Err(core::fmt::Error)
+    );
+    // the text that fit is kept
+    assert_eq!(buf.remaining_mut(), 2);
+    assert_eq!(&dst[..4], b"123-");
+}
+
+#[test]
+fn test_put_fmt_limit_overflow() {
+    let mut buf = BytesMut::new();
+    let mut limited = (&mut buf).limit(4);
+
+    assert!(limited.put_fmt(format_args!("{}", 1234)).is_ok());
+    assert!(limited.put_fmt(format_args!("{}", 5)).is_err());
+    assert_eq!(buf, "1234");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_chunks_mut_vectored() {
+    use std::io::IoSliceMut;
+
+    let mut data = [1u8; 8];
+    let mut slice = &mut data[..];
+    let mut dst = [IoSliceMut::new(&mut []), IoSliceMut::new(&mut [])];
+    assert_eq!(slice.chunks_mut_vectored(&mut dst), 1);
+    // a slice is handed out as is
+    assert_eq!(&dst[0][..], &[1; 8]);
+    assert_eq!(slice.chunks_mut_vectored(&mut []), 0);
+
+    let mut vec = Vec::with_capacity(8);
+    let mut dst = [IoSliceMut::new(&mut [])];
+    assert_eq!(vec.chunks_mut_vectored(&mut dst), 1);
+    // spare capacity is zeroed first
+    assert!(dst[0].len() >= 8);
+    assert!(dst[0].iter().all(|&b| b == 0));
+
+    // only a bounded prefix of a large spare capacity is zeroed
+    let mut vec = Vec::with_capacity(1 << 20);
+    let mut dst = [IoSliceMut::new(&mut [])];
+    assert_eq!(vec.chunks_mut_vectored(&mut dst), 1);
+    assert_eq!(dst[0].len(), 8 * 1024);
+    assert!(dst[0].iter().all(|&b| b == 0));
+
+    let mut empty = &mut [][..];
+    assert_eq!(empty.chunks_mut_vectored(&mut dst), 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_chunks_mut_vectored_limit() {
+    use bytes::buf::RingBuf;
+    use bytes::Buf;
+    use std::io::IoSliceMut;
+
+    let mut ring = RingBuf::with_capacity(8);
+    ring.put_slice(b"abcdef");
+    ring.advance(4);
+
+    // the free space is split in 2 and 6 bytes, limited to 5
+    {
+        let mut limited = (&mut ring).limit(5);
+        let mut dst = [
+            IoSliceMut::new(&mut []),
+            IoSliceMut::new(&mut []),
+            IoSliceMut::new(&mut []),
+        ];
+        assert_eq!(limited.chunks_mut_vectored(&mut dst), 2);
+        assert_eq!(dst[0].len(), 2);
+        assert_eq!(dst[1].len(), 3);
+    }
+
+    {
+        let mut limited = (&mut ring).limit(2);
+        let mut dst = [IoSliceMut::new(&mut []), IoSliceMut::new(&mut [])];
+        assert_eq!(limited.chunks_mut_vectored(&mut dst), 1);
+        assert_eq!(dst[0].len(), 2);
+    }
+
+    let mut limited = (&mut ring).limit(0);
+    let mut dst = [IoSliceMut::new(&mut [])];
+    assert_eq!(limited.chunks_mut_vectored(&mut dst), 0);
+}
@@ -0,0 +1,103 @@
+#![warn(rust_2018_idioms)]
+
+use bytes::{ByteStr, Bytes};
+
+use std::collections::HashMap;
+
+#[test]
+fn from_utf8() {
+    let bytes = Bytes::from(String::from("hello wörld"));
+    let ptr = bytes.as_ptr();
+
+    let s = ByteStr::from_utf8(bytes).unwrap();
+    assert_eq!(s, "hello wörld");
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.len(), 12);
+}
+
+#[test]
+fn from_utf8_invalid() {
+    let bytes = Bytes::from(b"ab\xc3".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let (bytes, err) = ByteStr::from_utf8(bytes).unwrap_err();
+    assert_eq!(bytes, &b"ab\xc3"[..]);
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(err.valid_up_to(), 2);
+    assert_eq!(err.error_len(), None);
+}
+
+#[test]
+fn conversions() {
+    let s = ByteStr::from(String::from("hello"));
+    assert_eq!(s, "hello");
+    assert_eq!(Bytes::from(s.clone()), &b"hello"[..]);
+    assert_eq!(s.into_bytes(), &b"hello"[..]);
+
+    let s = ByteStr::from("static");
+    assert_eq!(*s.as_bytes(), Bytes::from_static(b"static"));
+    assert_eq!(ByteStr::new(), "");
+}
+
+#[test]
+fn fmt() {
+    let s = ByteStr::from_static("a\"b");
+    assert_eq!(format!("{}", s), "a\"b");
+    assert_eq!(format!("{:?}", s), "\"a\\\"b\"");
+}
+
+#[test]
+fn slice() {
+    let s = ByteStr::from(String::from("aé€b"));
+
+    let b = s.slice(1..6);
+    assert_eq!(b, "é€");
+    assert_eq!(b.as_ptr(), unsafe { s.as_ptr().add(1) });
+
+    assert_eq!(s.slice(..=0), "a");
+    assert_eq!(s.slice(6..), "b");
+    assert_eq!(s.slice(7..), "");
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn slice_not_char_boundary() {
+    let s = ByteStr::from_static("aé");
+    let _ = s.slice(..2);
+}
+
+#[test]
+#[should_panic]
+fn slice_oob() {
+    let s = ByteStr::from_static("abc");
+    let _ = s.slice(2..4);
+}
+
+#[test]
+fn split_to() {
+    let mut s = ByteStr::from(String::from("€uro"));
+    let ptr = s.as_ptr();
+
+    let euro = s.split_to(3);
+    assert_eq!(euro, "€");
+    assert_eq!(euro.as_ptr(), ptr);
+    assert_eq!(s, "uro");
+
+    s.advance(1);
+    assert_eq!(s, "ro");
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn split_to_not_char_boundary() {
+    let mut s = ByteStr::from_static("€uro");
+    let _ = s.split_to(1);
+}
+
+#[test]
+fn hash_map_lookup() {
+    let mut map = HashMap::new();
+    map.insert(ByteStr::from_static("content-length"), 1);
+
+    assert_eq!(map.get("content-length"), Some(&1));
+}
@@ -0,0 +1,99 @@
+#![warn(rust_2018_idioms)]
+
+use bytes::{ByteStr, ByteString, BytesMut};
+
+use std::fmt::Write;
+
+#[test]
+fn push() {
+    let mut s = ByteString::new();
+    s.push_str("caf");
+    s.push('é');
+    s.push('!');
+
+    assert_eq!(s, "café!");
+    assert_eq!(s.len(), 6);
+}
+
+#[test]
+fn write_fmt() {
+    let mut s = ByteString::with_capacity(4);
+    write!(s, "id-{:04x}", 0xbeefu16).unwrap();
+    s.write_char('→').unwrap();
+
+    assert_eq!(s, "id-beef→");
+}
+
+#[test]
+fn from_utf8() {
+    let s = ByteString::from_utf8(BytesMut::from("ok")).unwrap();
+    assert_eq!(s, "ok");
+
+    let (bytes, err) = ByteString::from_utf8(BytesMut::from(&b"a\xe2\x82"[..])).unwrap_err();
+    assert_eq!(bytes, &b"a\xe2\x82"[..]);
+    assert_eq!(err.valid_up_to(), 1);
+}
+
+#[test]
+fn split_to_shares_storage() {
+    let mut s = ByteString::with_capacity(64);
+    s.push_str("first€\nsecond");
+    let ptr = s.as_ptr();
+
+    let first = s.split_to(9);
+    assert_eq!(first, "first€\n");
+    assert_eq!(first.as_ptr(), ptr);
+    assert_eq!(s, "second");
+    assert_eq!(s.as_ptr(), unsafe { ptr.add(9) });
+
+    let frozen = first.freeze();
+    assert_eq!(frozen.as_ptr(), ptr);
+
+    s.push_str(" line");
+    assert_eq!(s, "second line");
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn split_to_not_char_boundary() {
+    let mut s = ByteString::from("€");
+    let _ = s.split_to(1);
+}
+
+#[test]
+#[should_panic(expected = "char boundary")]
+fn truncate_not_char_boundary() {
+    let mut s = ByteString::from("a€");
+    s.truncate(2);
+}
+
+#[test]
+fn truncate() {
+    let mut s = ByteString::from("a€b");
+    s.truncate(4);
+    assert_eq!(s, "a€");
+    s.truncate(10);
+    assert_eq!(s, "a€");
+}
+
+#[test]
+fn from_string_is_zero_copy() {
+    let mut string = String::with_capacity(64);
+    string.push_str("hello");
+    let ptr = string.as_ptr();
+
+    let s = ByteString::from(string);
+    assert_eq!(s, "hello");
+    assert_eq!(s.as_ptr(), ptr);
+    assert_eq!(s.capacity(), 64);
+}
+
+#[test]
+fn freeze() {
+    let mut s = ByteString::from(String::from("hello"));
+    s.make_ascii_uppercase();
+
+    let frozen: ByteStr = s.freeze();
+    assert_eq!(frozen, "HELLO");
+    assert_eq!(BytesMut::from(ByteString::from("x")), &b"x"[..]);
+}
@@ -1,6 +1,6 @@
 #![warn(rust_2018_idioms)]
 
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut, TryReserveError};
 
 use std::usize;
 
@@ -574,6 +574,202 @@ fn reserve_shared_reuse() {
     assert_eq!(bytes.capacity(), 2009);
 }
 
+#[test]
+fn try_reserve_vec_recycling() {
+    let mut bytes = BytesMut::try_with_capacity(16).unwrap();
+    assert_eq!(bytes.capacity(), 16);
+    let addr = bytes.as_ptr() as usize;
+    bytes.put("0123456789012345".as_bytes());
+    bytes.advance(10);
+    bytes.try_reserve(8).unwrap();
+    assert_eq!(bytes.capacity(), 16);
+    assert_eq!(bytes.as_ptr() as usize, addr);
+    assert_eq!(&*bytes, b"012345");
+
+    bytes.try_reserve(100).unwrap();
+    assert!(bytes.capacity() >= 106);
+    assert_eq!(&*bytes, b"012345");
+}
+
+#[test]
+fn try_reserve_shared_reuse() {
+    let mut bytes = BytesMut::with_capacity(1000);
+    bytes.put_slice(b"Hello, World!");
+    drop(bytes.split());
+
+    bytes.put_slice(b"!123ex123,sadchELLO,_wORLD!");
+    drop(bytes.split_off(9));
+
+    bytes.try_reserve(2000).unwrap();
+    assert_eq!(&*bytes, b"!123ex123");
+    assert_eq!(bytes.capacity(), 2009);
+}
+
+#[test]
+fn try_reserve_shared_copies() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.put_slice(b"hello world");
+    let other = bytes.split_to(6);
+
+    bytes.try_reserve(128).unwrap();
+    assert!(bytes.capacity() >= 133);
+    assert_eq!(&*bytes, b"world");
+    assert_eq!(&*other, b"hello ");
+}
+
+#[test]
+fn try_reserve_overflow() {
+    let mut bytes = BytesMut::with_capacity(1024);
+    bytes.put_slice(b"hello world");
+
+    assert_eq!(
+        bytes.try_reserve(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(
+        bytes.try_reserve(isize::MAX as usize),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(&*bytes, b"hello world");
+
+    // shared buffer
+    let other = bytes.split_to(6);
+    assert_eq!(
+        bytes.try_reserve(usize::MAX),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(&*bytes, b"world");
+    assert_eq!(&*other, b"hello ");
+
+    assert_eq!(
+        BytesMut::try_with_capacity(isize::MAX as usize + 1).unwrap_err(),
+        TryReserveError::CapacityOverflow
+    );
+}
+
+#[test]
+fn try_reserve_overflow_unique_shared() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.put_slice(b"hello world");
+    drop(bytes.split_to(6));
+
+    // fits in `usize` on its own, but not once the offset into the shared
+    // vector is added
+    let additional = usize::MAX - bytes.len();
+    assert_eq!(
+        bytes.try_reserve(additional),
+        Err(TryReserveError::CapacityOverflow)
+    );
+    assert_eq!(bytes.capacity(), 58);
+    assert_eq!(&*bytes, b"world");
+}
+
+#[test]
+fn try_with_capacity_alloc_error() {
+    match BytesMut::try_with_capacity(isize::MAX as usize) {
+        Err(TryReserveError::AllocError { layout }) => {
+            assert_eq!(layout.size(), isize::MAX as usize)
+        }
+        other => panic!("unexpected result: {:?}", other.map(|b| b.capacity())),
+    }
+}
+
+#[test]
+fn try_reclaim_vec() {
+    let mut bytes = BytesMut::with_capacity(16);
+    let addr = bytes.as_ptr() as usize;
+    bytes.put("0123456789012345".as_bytes());
+    bytes.advance(10);
+
+    assert!(bytes.try_reclaim(6));
+    assert!(bytes.try_reclaim(10));
+    assert_eq!(bytes.capacity(), 16);
+    assert_eq!(bytes.as_ptr() as usize, addr);
+    assert_eq!(&*bytes, b"012345");
+
+    // would have to allocate
+    assert!(!bytes.try_reclaim(11));
+    assert_eq!(bytes.capacity(), 16);
+    assert_eq!(bytes.as_ptr() as usize, addr);
+}
+
+#[test]
+fn try_reclaim_vec_too_little_consumed() {
+    let mut bytes = BytesMut::with_capacity(16);
+    bytes.put("0123456789012345".as_bytes());
+    bytes.advance(4);
+
+    // moving the 12 remaining bytes back costs more than the 4 reclaimed
+    assert!(!bytes.try_reclaim(4));
+    assert_eq!(bytes.capacity(), 12);
+    assert_eq!(&*bytes, b"456789012345");
+}
+
+#[test]
+fn try_reclaim_arc() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.put_slice(&[b'x'; 48]);
+    let frozen = bytes.split_to(40).freeze();
+    let addr = frozen.as_ptr() as usize;
+
+    assert!(!bytes.try_reclaim(48));
+    assert_eq!(bytes.capacity(), 24);
+
+    drop(frozen);
+    assert!(bytes.try_reclaim(48));
+    assert_eq!(bytes.capacity(), 64);
+    assert_eq!(bytes.as_ptr() as usize, addr);
+    assert_eq!(&*bytes, &[b'x'; 8][..]);
+
+    assert!(!bytes.try_reclaim(57));
+    assert!(!bytes.try_reclaim(usize::MAX));
+    assert_eq!(bytes.as_ptr() as usize, addr);
+}
+
+#[test]
+fn try_reclaim_overflow() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.put_slice(b"hello world");
+    drop(bytes.split_to(6));
+
+    // the offset into the shared vector pushes this past `usize::MAX`
+    assert!(!bytes.try_reclaim(usize::MAX - bytes.len()));
+    assert_eq!(bytes.capacity(), 58);
+    assert_eq!(&*bytes, b"world");
+}
+
+#[test]
+fn try_reclaim_split_off() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.put_slice(b"hello world");
+    let tail = bytes.split_off(5);
+
+    assert!(!bytes.try_reclaim(16));
+    assert_eq!(bytes.capacity(), 5);
+
+    drop(tail);
+    assert!(bytes.try_reclaim(16));
+    assert_eq!(bytes.capacity(), 21);
+    assert_eq!(&*bytes, b"hello");
+}
+
+#[test]
+fn try_extend_from_slice_mut() {
+    let mut bytes = BytesMut::new();
+    bytes.try_extend_from_slice(&LONG[..3]).unwrap();
+    bytes.try_extend_from_slice(&LONG[3..]).unwrap();
+    assert_eq!(LONG[..], *bytes);
+}
+
+#[test]
+fn try_copy_from_slice() {
+    let bytes = Bytes::try_copy_from_slice(LONG).unwrap();
+    assert_eq!(bytes, LONG[..]);
+
+    let empty = Bytes::try_copy_from_slice(b"").unwrap();
+    assert!(empty.is_empty());
+}
+
 #[test]
 fn extend_mut() {
     let mut bytes = BytesMut::with_capacity(0);
@@ -1163,3 +1359,868 @@ fn test_bytes_into_vec_promotable_even() {
     assert_eq!(Vec::from(b2), vec[20..]);
     assert_eq!(Vec::from(b1), vec[..20]);
 }
+
+#[derive(Clone)]
+struct SharedAtomicCounter(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+impl SharedAtomicCounter {
+    fn new() -> Self {
+        SharedAtomicCounter(std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)))
+    }
+
+    fn increment(&self) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(std::sync::atomic::Ordering::Acquire)
+    }
+}
+
+struct OwnedTester {
+    buf: [u8; 5],
+    drop_count: SharedAtomicCounter,
+}
+
+impl OwnedTester {
+    fn new(buf: [u8; 5], drop_count: SharedAtomicCounter) -> Self {
+        Self { buf, drop_count }
+    }
+}
+
+impl AsRef<[u8]> for OwnedTester {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..]
+    }
+}
+
+impl Drop for OwnedTester {
+    fn drop(&mut self) {
+        self.drop_count.increment();
+    }
+}
+
+#[test]
+fn owned_basic() {
+    let buf: [u8; 5] = [1, 2, 3, 4, 5];
+    let drop_counter = SharedAtomicCounter::new();
+    let owner = OwnedTester::new(buf, drop_counter.clone());
+    let b1 = Bytes::from_owner(owner);
+    assert_eq!(&buf[..], &b1[..]);
+    drop(b1);
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn owned_to_vec() {
+    let buf: [u8; 5] = [1, 2, 3, 4, 5];
+    let drop_counter = SharedAtomicCounter::new();
+    let owner = OwnedTester::new(buf, drop_counter.clone());
+    let b1 = Bytes::from_owner(owner);
+
+    let v1 = Vec::from(b1);
+    assert_eq!(&v1[..], &buf[..]);
+    // Converting to a `Vec` copies and releases the owner.
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn owned_clone_and_slice() {
+    let buf: [u8; 5] = [1, 2, 3, 4, 5];
+    let drop_counter = SharedAtomicCounter::new();
+    let owner = OwnedTester::new(buf, drop_counter.clone());
+    let mut b1 = Bytes::from_owner(owner);
+    let b2 = b1.clone();
+    let b3 = b1.slice(1..4);
+    let b4 = b1.split_to(2);
+
+    assert_eq!(&b2[..], &buf[..]);
+    assert_eq!(&b3[..], &buf[1..4]);
+    assert_eq!(&b4[..], &buf[..2]);
+    assert_eq!(&b1[..], &buf[2..]);
+    assert_eq!(b3.as_ptr(), unsafe { b2.as_ptr().add(1) });
+
+    drop(b1);
+    drop(b2);
+    drop(b3);
+    assert_eq!(drop_counter.get(), 0);
+    drop(b4);
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn owned_dropped_exactly_once_across_threads() {
+    let buf: [u8; 5] = [1, 2, 3, 4, 5];
+    let drop_counter = SharedAtomicCounter::new();
+    let owner = OwnedTester::new(buf, drop_counter.clone());
+    let b1 = Bytes::from_owner(owner);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let b = b1.clone();
+            std::thread::spawn(move || {
+                let c = b.clone();
+                assert_eq!(&c[..], &[1, 2, 3, 4, 5][..]);
+            })
+        })
+        .collect();
+    drop(b1);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn try_into_mut_promotable() {
+    // Test cases where kind == KIND_VEC
+    let vec = b"hello world".to_vec();
+    let ptr = vec.as_ptr();
+    let b = Bytes::from(vec);
+    let m = b.try_into_mut().unwrap();
+    assert_eq!(m, b"hello world"[..]);
+    assert_eq!(m.as_ptr(), ptr);
+    assert_eq!(m.capacity(), 11);
+
+    // Test cases where kind == KIND_VEC and offset != 0
+    let mut b = Bytes::from(b"hello world".to_vec());
+    b.advance(6);
+    let ptr = b.as_ptr();
+    let mut m = b.try_into_mut().unwrap();
+    assert_eq!(m, b"world"[..]);
+    assert_eq!(m.as_ptr(), ptr);
+    m.extend_from_slice(b"!");
+    assert_eq!(m, b"world!"[..]);
+
+    // Test cases where kind == KIND_ARC, ref_cnt == 2
+    let b1 = Bytes::from(b"hello world".to_vec());
+    let b2 = b1.clone();
+    let b1 = b1.try_into_mut().unwrap_err();
+    assert_eq!(b1, b"hello world"[..]);
+
+    // Test cases where kind == KIND_ARC, ref_cnt == 1
+    drop(b2);
+    let ptr = b1.as_ptr();
+    let m = b1.try_into_mut().unwrap();
+    assert_eq!(m, b"hello world"[..]);
+    assert_eq!(m.as_ptr(), ptr);
+}
+
+#[test]
+fn try_into_mut_shared_after_split() {
+    let mut b1 = Bytes::from(b"hello world".to_vec());
+    let b2 = b1.split_off(5);
+    let b1 = b1.try_into_mut().unwrap_err();
+    drop(b2);
+
+    let ptr = b1.as_ptr();
+    let mut m = b1.try_into_mut().unwrap();
+    assert_eq!(m, b"hello"[..]);
+    assert_eq!(m.as_ptr(), ptr);
+    assert_eq!(m.capacity(), 11);
+
+    m.put_slice(b"!");
+    assert_eq!(m, b"hello!"[..]);
+}
+
+#[test]
+fn try_into_mut_from_bytes_mut() {
+    let mut m = BytesMut::with_capacity(64);
+    m.put_slice(b"hello world");
+    let rest = m.split_off(11);
+
+    let b1 = m.freeze();
+    let b2 = b1.clone();
+    let b1 = b1.try_into_mut().unwrap_err();
+    drop(b2);
+    let b1 = b1.try_into_mut().unwrap_err();
+
+    // Only the split off tail was pinning the buffer.
+    drop(rest);
+    let ptr = b1.as_ptr();
+    let m = b1.try_into_mut().unwrap();
+    assert_eq!(m, b"hello world"[..]);
+    assert_eq!(m.as_ptr(), ptr);
+    assert_eq!(m.capacity(), 64);
+}
+
+#[test]
+fn try_into_mut_static_and_owned() {
+    let b = Bytes::from_static(b"hello");
+    assert_eq!(b.try_into_mut().unwrap_err(), b"hello"[..]);
+
+    let drop_counter = SharedAtomicCounter::new();
+    let b = Bytes::from_owner(OwnedTester::new([1, 2, 3, 4, 5], drop_counter.clone()));
+    let b = b.try_into_mut().unwrap_err();
+    assert_eq!(b, [1, 2, 3, 4, 5][..]);
+    drop(b);
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn is_unique() {
+    let b = Bytes::from_static(b"hello");
+    assert!(!b.is_unique());
+
+    let b1 = Bytes::from(b"hello".to_vec());
+    assert!(b1.is_unique());
+    let b2 = b1.slice(1..);
+    assert!(!b1.is_unique());
+    assert!(!b2.is_unique());
+    drop(b1);
+    assert!(b2.is_unique());
+
+    let mut m = BytesMut::from(&b"hello world"[..]);
+    let rest = m.split_off(5);
+    let b = m.freeze();
+    assert!(!b.is_unique());
+    drop(rest);
+    assert!(b.is_unique());
+
+    let drop_counter = SharedAtomicCounter::new();
+    let b = Bytes::from_owner(OwnedTester::new([1, 2, 3, 4, 5], drop_counter));
+    assert!(!b.is_unique());
+}
+
+#[test]
+fn info() {
+    let info = Bytes::from_static(b"hello").info();
+    assert!(info.is_static());
+    assert_eq!(info.ref_count(), None);
+    assert_eq!(info.capacity(), None);
+
+    let mut b1 = Bytes::from(vec![0; 64]);
+    b1.advance(16);
+    let info = b1.info();
+    assert!(info.is_vec());
+    assert_eq!(info.ref_count(), Some(1));
+    assert_eq!(info.capacity(), Some(64));
+
+    let b2 = b1.slice(..8);
+    let info = b2.info();
+    assert!(info.is_shared());
+    assert_eq!(info.ref_count(), Some(2));
+    assert_eq!(info.capacity(), Some(64));
+    assert_eq!(b1.info(), info);
+
+    let mut m = BytesMut::with_capacity(1024);
+    m.put_slice(b"hello world");
+    let b = m.split().freeze();
+    let info = b.info();
+    assert!(info.is_shared());
+    assert_eq!(info.ref_count(), Some(2));
+    assert_eq!(info.capacity(), Some(1024));
+    drop(m);
+    assert_eq!(b.info().ref_count(), Some(1));
+
+    let drop_counter = SharedAtomicCounter::new();
+    let b1 = Bytes::from_owner(OwnedTester::new([1, 2, 3, 4, 5], drop_counter));
+    let b2 = b1.clone();
+    let info = b2.info();
+    assert!(info.is_owned());
+    assert_eq!(info.ref_count(), Some(2));
+    assert_eq!(info.capacity(), None);
+}
+
+#[test]
+fn weak_promotable() {
+    let b = Bytes::from(b"hello world".to_vec());
+    let weak = b.slice(6..).downgrade();
+    assert_eq!(weak.len(), 5);
+    assert!(b.info().is_shared());
+    assert_eq!(b.info().ref_count(), Some(1));
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded, b"world"[..]);
+    assert_eq!(upgraded.as_ptr(), unsafe { b.as_ptr().add(6) });
+    assert_eq!(b.info().ref_count(), Some(2));
+
+    drop(upgraded);
+    let weak2 = weak.clone();
+    drop(b);
+    assert!(weak.upgrade().is_none());
+    assert!(weak2.upgrade().is_none());
+}
+
+#[test]
+fn weak_static() {
+    let b = Bytes::from_static(b"hello");
+    let weak = b.downgrade();
+    drop(b);
+    assert_eq!(weak.upgrade().unwrap(), b"hello"[..]);
+}
+
+#[test]
+fn weak_owned() {
+    let drop_counter = SharedAtomicCounter::new();
+    let b = Bytes::from_owner(OwnedTester::new([1, 2, 3, 4, 5], drop_counter.clone()));
+    let weak = b.slice(1..3).downgrade();
+
+    assert_eq!(weak.upgrade().unwrap(), [2, 3][..]);
+    assert_eq!(drop_counter.get(), 0);
+
+    drop(b);
+    // The owner is dropped as soon as the last strong handle goes away.
+    assert_eq!(drop_counter.get(), 1);
+    assert!(weak.upgrade().is_none());
+    drop(weak);
+    assert_eq!(drop_counter.get(), 1);
+}
+
+#[test]
+fn weak_bytes_mut() {
+    let mut m = BytesMut::with_capacity(64);
+    m.put_slice(b"hello world");
+    let b = m.split().freeze();
+    let weak = b.downgrade();
+    drop(b);
+
+    // `m` still keeps the buffer alive, but must not reuse it while the weak
+    // reference could be upgraded.
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded, b"hello world"[..]);
+    let ptr = upgraded.as_ptr();
+    drop(upgraded);
+
+    m.reserve(64);
+    assert_ne!(m.as_ptr(), ptr);
+
+    // Reserving moved `m` to a new buffer, releasing the old one.
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_bytes_mut_released() {
+    let mut m = BytesMut::with_capacity(64);
+    m.put_slice(b"hello world");
+    let b = m.split().freeze();
+    let weak = b.downgrade();
+    drop(b);
+    drop(m);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_does_not_prevent_try_into_mut() {
+    let b = Bytes::from(b"hello".to_vec());
+    let weak = b.downgrade();
+    assert!(b.is_unique());
+    let m = b.try_into_mut().unwrap();
+    assert_eq!(m, b"hello"[..]);
+    assert!(weak.upgrade().is_none());
+
+    let mut m = BytesMut::with_capacity(64);
+    m.put_slice(b"hello");
+    let rest = m.split_off(5);
+    let b = m.freeze();
+    let weak = b.downgrade();
+    drop(rest);
+
+    let ptr = b.as_ptr();
+    let mut m = b.try_into_mut().unwrap();
+    assert_eq!(m.as_ptr(), ptr);
+    assert!(weak.upgrade().is_none());
+
+    // The buffer is no longer shared with the weak reference, so it can be
+    // reused in place.
+    m.clear();
+    m.reserve(64);
+    assert_eq!(m.as_ptr(), ptr);
+}
+
+#[test]
+fn weak_upgrade_races_try_into_mut() {
+    for _ in 0..16 {
+        let b = Bytes::from(b"hello world".to_vec());
+        let weak = b.downgrade();
+
+        // A handle upgraded from the weak reference keeps the buffer shared.
+        let upgraded = weak.upgrade().unwrap();
+        let b = b.try_into_mut().unwrap_err();
+        drop(upgraded);
+
+        let th = std::thread::spawn(move || {
+            let mut taken = false;
+            for _ in 0..100 {
+                match weak.upgrade() {
+                    Some(b) => {
+                        // Once the buffer was taken it must never come back.
+                        assert!(!taken);
+                        assert_eq!(b, b"hello world"[..]);
+                    }
+                    None => taken = true,
+                }
+            }
+            weak
+        });
+
+        let mut b = b;
+        let mut m = loop {
+            match b.try_into_mut() {
+                Ok(m) => break m,
+                Err(shared) => b = shared,
+            }
+        };
+
+        // The weak reference can no longer observe writes to the taken buffer.
+        m.clear();
+        m.put_slice(b"HELLO WORLD");
+
+        let weak = th.join().unwrap();
+        assert!(weak.upgrade().is_none());
+    }
+}
+
+#[test]
+fn weak_upgrade_races_reserve() {
+    for _ in 0..16 {
+        let mut m = BytesMut::with_capacity(64);
+        m.put_slice(b"hello world");
+        let b = m.split().freeze();
+        let weak = b.downgrade();
+        drop(b);
+
+        let th = std::thread::spawn(move || {
+            for _ in 0..100 {
+                if let Some(b) = weak.upgrade() {
+                    assert_eq!(b, b"hello world"[..]);
+                }
+            }
+            weak
+        });
+
+        // `m` is the only strong handle, but must not reclaim the buffer in
+        // place while the weak reference can still be upgraded.
+        for _ in 0..100 {
+            m.clear();
+            m.reserve(64);
+            m.put_slice(b"HELLO WORLD");
+        }
+
+        let weak = th.join().unwrap();
+        assert!(weak.upgrade().is_none());
+    }
+}
+
+#[test]
+fn weak_to_vec() {
+    let mut m = BytesMut::with_capacity(64);
+    m.put_slice(b"hello");
+    let b = m.split().freeze();
+    drop(m);
+    let weak = b.downgrade();
+
+    assert_eq!(Vec::from(b), b"hello");
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_across_threads() {
+    for _ in 0..16 {
+        let mut m = BytesMut::with_capacity(64);
+        m.put_slice(b"hello world");
+        let b = m.split().freeze();
+        let weak = b.downgrade();
+
+        let th = std::thread::spawn(move || {
+            for _ in 0..100 {
+                if let Some(b) = weak.upgrade() {
+                    assert_eq!(b, b"hello world"[..]);
+                }
+            }
+            weak
+        });
+
+        for _ in 0..100 {
+            m.reserve(64);
+        }
+        drop(b);
+        drop(m);
+
+        let weak = th.join().unwrap();
+        assert!(weak.upgrade().is_none());
+    }
+}
+
+#[test]
+fn get_prefixed_bytes_is_zero_copy() {
+    let mut bytes = Bytes::from(b"\x00\x05hello world".to_vec());
+    let ptr = bytes.as_ptr();
+    let hello = bytes.get_u16_prefixed_bytes();
+    assert_eq!(hello, b"hello"[..]);
+    assert_eq!(hello.as_ptr(), unsafe { ptr.add(2) });
+
+    let mut bytes_mut = BytesMut::from(&b"\x05hello world"[..]);
+    let ptr = bytes_mut.as_ptr();
+    let hello = bytes_mut.get_uvarint_prefixed_bytes();
+    assert_eq!(hello, b"hello"[..]);
+    assert_eq!(hello.as_ptr(), unsafe { ptr.add(1) });
+    assert_eq!(bytes_mut, b" world"[..]);
+}
+
+#[test]
+fn placeholder_fill() {
+    let mut buf = BytesMut::new();
+    let a = buf.put_placeholder::<u8>();
+    let b = buf.put_placeholder::<u16>();
+    let c = buf.put_placeholder::<u64>();
+    buf.put_slice(b"xy");
+
+    assert_eq!(buf.len_since(&a), 12);
+    assert_eq!(buf.len_since(&c), 2);
+
+    buf.fill_u8(a, 1);
+    buf.fill_u16_le(b, 0x0302);
+    buf.fill_u64_be(c, 0x0405_0607_0809_0a0b);
+    assert_eq!(buf, b"\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0bxy"[..]);
+}
+
+#[test]
+fn placeholder_survives_reserve() {
+    let mut buf = BytesMut::with_capacity(4);
+    buf.put_u8(0xff);
+    let len = buf.put_placeholder::<u32>();
+    let ptr = buf.as_ptr();
+
+    buf.put_slice(&[b'a'; 1024]);
+    assert_ne!(ptr, buf.as_ptr());
+
+    let n = buf.len_since(&len) as u32;
+    buf.fill_u32_be(len, n);
+    assert_eq!(buf[..5], b"\xff\x00\x00\x04\x00"[..]);
+}
+
+#[test]
+#[should_panic]
+fn placeholder_split_off() {
+    let mut buf = BytesMut::new();
+    let len = buf.put_placeholder::<u32>();
+    buf.put_u16(0);
+
+    let _frame = buf.split_to(6);
+    buf.fill_u32_be(len, 2);
+}
+
+#[test]
+fn placeholder_after_split_to() {
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"HDR");
+    let len = buf.put_placeholder::<u32>();
+    buf.put_slice(b"body-body-body");
+
+    let hdr = buf.split_to(3);
+    assert_eq!(buf.len_since(&len), 14);
+    buf.fill_u32_be(len, 0xAABB_CCDD);
+
+    assert_eq!(hdr, b"HDR"[..]);
+    assert_eq!(buf, b"\xAA\xBB\xCC\xDDbody-body-body"[..]);
+}
+
+#[test]
+fn placeholder_after_advance_and_reserve() {
+    let mut buf = BytesMut::with_capacity(8);
+    buf.put_slice(b"HDR");
+    let len = buf.put_placeholder::<u16>();
+    buf.advance(2);
+
+    let ptr = buf.as_ptr();
+    buf.put_slice(&[b'a'; 1024]);
+    assert_ne!(ptr, buf.as_ptr());
+
+    buf.fill_u16_le(len, 0x0201);
+    assert_eq!(buf[..4], b"R\x01\x02a"[..]);
+}
+
+#[test]
+fn placeholder_after_split_to_and_reserve() {
+    // the split off header is still alive, so `reserve` copies the data to a
+    // new allocation
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"HDR");
+    let len = buf.put_placeholder::<u32>();
+    buf.put_slice(b"body-body-body");
+
+    let hdr = buf.split_to(3);
+    let ptr = buf.as_ptr();
+    buf.reserve(4096);
+    assert_ne!(ptr, buf.as_ptr());
+
+    buf.fill_u32_be(len, 0xAABB_CCDD);
+    assert_eq!(hdr, b"HDR"[..]);
+    assert_eq!(buf, b"\xAA\xBB\xCC\xDDbody-body-body"[..]);
+
+    // the header is gone, so `reserve` reallocates the unique buffer
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"HDR");
+    let len = buf.put_placeholder::<u32>();
+    buf.put_slice(b"body-body-body");
+
+    drop(buf.split_to(3));
+    buf.reserve(4096);
+
+    buf.fill_u32_be(len, 0xAABB_CCDD);
+    assert_eq!(buf, b"\xAA\xBB\xCC\xDDbody-body-body"[..]);
+}
+
+#[test]
+fn placeholder_after_advance_and_reclaim() {
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_slice(&[b'x'; 40]);
+    let len = buf.put_placeholder::<u16>();
+    buf.put_slice(b"ab");
+    let ptr = buf.as_ptr();
+
+    // `reserve` moves the data back to the start of the allocation
+    buf.advance(40);
+    buf.reserve(30);
+    assert_eq!(ptr, buf.as_ptr());
+
+    assert_eq!(buf.len_since(&len), 2);
+    buf.fill_u16_be(len, 0x0102);
+    assert_eq!(buf, b"\x01\x02ab"[..]);
+}
+
+#[test]
+#[should_panic(expected = "placeholder was split off")]
+fn placeholder_split_off_after_reclaim() {
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_slice(&[b'x'; 40]);
+    let len = buf.put_placeholder::<u16>();
+    buf.put_slice(b"ab");
+
+    buf.advance(40);
+    buf.reserve(30);
+    buf.put_slice(&[b'y'; 40]);
+    buf.advance(3);
+    buf.fill_u16_be(len, 0x0102);
+}
+
+#[test]
+#[should_panic(expected = "placeholder does not belong to this buffer")]
+fn placeholder_other_buffer() {
+    let mut buf = BytesMut::new();
+    let len = buf.put_placeholder::<u16>();
+
+    let mut other = BytesMut::from(&b"abcd"[..]);
+    other.fill_u16_be(len, 0);
+}
+
+#[test]
+#[should_panic(expected = "placeholder was split off")]
+fn placeholder_split_off_buffer_still_long_enough() {
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"HDR");
+    let len = buf.put_placeholder::<u32>();
+    buf.put_slice(b"body-body-body");
+
+    let _frame = buf.split_to(5);
+    buf.fill_u32_be(len, 0xAABB_CCDD);
+}
+
+#[test]
+#[should_panic]
+fn placeholder_truncated() {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0);
+    let len = buf.put_placeholder::<u16>();
+    buf.truncate(2);
+    buf.fill_u16_be(len, 0);
+}
+
+#[test]
+fn split_to_delimiter() {
+    let mut bytes = Bytes::from(b"a\r\n\r\nbc\r".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let a = bytes.split_to_delimiter(b"\r\n").unwrap();
+    assert_eq!(a, b"a"[..]);
+    assert_eq!(a.as_ptr(), ptr);
+    assert_eq!(bytes.split_to_delimiter(b"\r\n").unwrap(), b""[..]);
+    assert_eq!(bytes.split_to_delimiter(b"\r\n"), None);
+    assert_eq!(bytes, b"bc\r"[..]);
+    assert_eq!(bytes.as_ptr(), unsafe { ptr.add(5) });
+}
+
+#[test]
+fn split_to_delimiter_mut() {
+    let mut buf = BytesMut::with_capacity(64);
+    buf.put_slice(b"key:val");
+
+    assert_eq!(buf.split_to_delimiter(b"\r\n"), None);
+    assert_eq!(buf, b"key:val"[..]);
+
+    buf.put_slice(b"ue\r\nrest");
+    let ptr = buf.as_ptr();
+    let line = buf.split_to_delimiter(b"\r\n").unwrap();
+    assert_eq!(line, b"key:value"[..]);
+    assert_eq!(line.as_ptr(), ptr);
+    assert_eq!(buf, b"rest"[..]);
+}
+
+#[test]
+#[should_panic]
+fn split_to_empty_delimiter() {
+    let mut bytes = Bytes::from_static(b"abc");
+    let _ = bytes.split_to_delimiter(b"");
+}
+
+#[test]
+fn split_iter() {
+    let bytes = Bytes::from(b"a,bc,,d,".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let fields: Vec<Bytes> = bytes.split(|&b| b == b',').collect();
+    assert_eq!(fields, [&b"a"[..], b"bc", b"", b"d", b""]);
+    assert_eq!(fields[1].as_ptr(), unsafe { ptr.add(2) });
+    assert_eq!(bytes, b"a,bc,,d,"[..]);
+
+    let fields: Vec<Bytes> = Bytes::new().split(|&b| b == b',').collect();
+    assert_eq!(fields, [&b""[..]]);
+}
+
+#[test]
+fn splitn_iter() {
+    let bytes = Bytes::from_static(b"a b c");
+    let sp = |&b: &u8| b == b' ';
+
+    assert_eq!(bytes.splitn(0, sp).count(), 0);
+    assert_eq!(bytes.splitn(1, sp).collect::<Vec<_>>(), [&b"a b c"[..]]);
+    assert_eq!(bytes.splitn(2, sp).collect::<Vec<_>>(), [&b"a"[..], b"b c"]);
+    assert_eq!(
+        bytes.splitn(5, sp).collect::<Vec<_>>(),
+        [&b"a"[..], b"b", b"c"]
+    );
+}
+
+#[test]
+fn split_whitespace_iter() {
+    let bytes = Bytes::from_static(b"  one\ttwo \r\n three  ");
+    let words: Vec<Bytes> = bytes.split_whitespace().collect();
+    assert_eq!(words, [&b"one"[..], b"two", b"three"]);
+
+    assert_eq!(Bytes::from_static(b" \n ").split_whitespace().next(), None);
+}
+
+#[test]
+fn chunks_exact_iter() {
+    let bytes = Bytes::from(b"abcdefgh".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let mut chunks = bytes.chunks_exact(3);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks.remainder(), &b"gh"[..]);
+    assert_eq!(chunks.next().unwrap(), b"abc"[..]);
+    let def = chunks.next().unwrap();
+    assert_eq!(def, b"def"[..]);
+    assert_eq!(def.as_ptr(), unsafe { ptr.add(3) });
+    assert_eq!(chunks.next(), None);
+
+    assert_eq!(bytes.chunks_exact(8).count(), 1);
+    assert_eq!(bytes.chunks_exact(9).count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn chunks_exact_zero() {
+    let _ = Bytes::from_static(b"abc").chunks_exact(0);
+}
+
+#[cfg(feature = "std")]
+struct Trickle<'a> {
+    src: &'a [u8],
+    interrupt: bool,
+    // bytes of the last `buf` past what was returned
+    seen_tail: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+
+        self.seen_tail = buf.to_vec();
+        let n = std::cmp::min(3, std::cmp::min(buf.len(), self.src.len()));
+        buf[..n].copy_from_slice(&self.src[..n]);
+        // scribble over the rest, which must not be zeroed again
+        for b in &mut buf[n..] {
+            *b = 0xaa;
+        }
+        self.src = &self.src[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn read_from() {
+    let mut buf = BytesMut::from(&b"ab"[..]);
+    let mut src = &b"cdefgh"[..];
+
+    assert_eq!(buf.read_from(&mut src, 4).unwrap(), 4);
+    assert_eq!(buf, &b"abcdef"[..]);
+    assert_eq!(buf.read_from(&mut src, 0).unwrap(), 0);
+    assert_eq!(buf.read_from(&mut src, 10).unwrap(), 2);
+    assert_eq!(buf.read_from(&mut src, 10).unwrap(), 0);
+    assert_eq!(buf, &b"abcdefgh"[..]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn read_to_end_from() {
+    let data: Vec<u8> = (0..200).collect();
+    let mut src = Trickle {
+        src: &data,
+        interrupt: false,
+        seen_tail: vec![],
+    };
+
+    let mut buf = BytesMut::with_capacity(16);
+    buf.extend_from_slice(b"xy");
+    assert_eq!(buf.read_to_end_from(&mut src).unwrap(), 200);
+    assert_eq!(&buf[..2], b"xy");
+    assert_eq!(&buf[2..], &data[..]);
+
+    // the last read saw the scribbles of the one before
+    assert!(src.seen_tail.iter().all(|&b| b == 0xaa));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn read_to_end_from_shared() {
+    let mut buf = BytesMut::with_capacity(8);
+    buf.extend_from_slice(b"head");
+    let other = buf.split_to(2);
+
+    let mut src = &b"0123456789"[..];
+    assert_eq!(buf.read_to_end_from(&mut src).unwrap(), 10);
+    assert_eq!(buf, &b"ad0123456789"[..]);
+    assert_eq!(other, &b"he"[..]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn read_exact_from() {
+    let data: Vec<u8> = (0..20).collect();
+    let mut src = Trickle {
+        src: &data,
+        interrupt: false,
+        seen_tail: vec![],
+    };
+
+    let mut buf = BytesMut::new();
+    buf.read_exact_from(&mut src, 8).unwrap();
+    assert_eq!(buf, &data[..8]);
+    // a read of 3 bytes left the last 2 scribbled over
+    assert_eq!(src.seen_tail, [0xaa, 0xaa]);
+
+    let err = buf.read_exact_from(&mut src, 13).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(buf, &data[..]);
+
+    buf.read_exact_from(&mut src, 0).unwrap();
+    assert_eq!(buf.len(), 20);
+}
@@ -0,0 +1,172 @@
+#![warn(rust_2018_idioms)]
+
+use bytes::{Buf, Bytes, BytesRope};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+fn to_vec(rope: &BytesRope) -> Vec<u8> {
+    let mut rope = rope.clone();
+    let mut v = Vec::with_capacity(rope.remaining());
+    while rope.has_remaining() {
+        let n = rope.chunk().len();
+        v.extend_from_slice(rope.chunk());
+        rope.advance(n);
+    }
+    v
+}
+
+#[test]
+fn from_bytes_is_single_leaf() {
+    let bytes = Bytes::from(b"hello world".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let rope = BytesRope::from(bytes);
+    assert_eq!(rope.len(), 11);
+    assert_eq!(rope.chunk().as_ptr(), ptr);
+    assert_eq!(rope.chunk().len(), 11);
+
+    assert!(BytesRope::from(Bytes::new()).is_empty());
+}
+
+#[test]
+fn insert_remove_slice() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"hello world"));
+    rope.insert(0, Bytes::from_static(b">> "));
+    rope.insert(14, Bytes::from_static(b"!"));
+    rope.insert(8, Bytes::new());
+    assert_eq!(to_vec(&rope), b">> hello world!");
+
+    rope.remove(2..9);
+    assert_eq!(to_vec(&rope), b">>world!");
+    rope.remove(..=1);
+    assert_eq!(to_vec(&rope), b"world!");
+
+    let slice = rope.slice(1..4);
+    assert_eq!(to_vec(&slice), b"orl");
+    assert_eq!(to_vec(&rope.slice(..)), b"world!");
+    assert!(rope.slice(3..3).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn insert_out_of_bounds() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"abc"));
+    rope.insert(4, Bytes::from_static(b"d"));
+}
+
+#[test]
+#[should_panic]
+fn remove_out_of_bounds() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"abc"));
+    rope.remove(2..4);
+}
+
+#[test]
+fn slice_shares_leaves() {
+    let bytes = Bytes::from(b"0123456789".to_vec());
+    let ptr = bytes.as_ptr();
+
+    let rope = BytesRope::from(bytes);
+    let slice = rope.slice(3..7);
+    assert_eq!(slice.chunk(), b"3456");
+    assert_eq!(slice.chunk().as_ptr(), unsafe { ptr.add(3) });
+}
+
+#[test]
+fn clone_is_unaffected_by_edits() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"abc"));
+    let snapshot = rope.clone();
+
+    rope.insert(3, Bytes::from_static(b"def"));
+    rope.remove(..1);
+
+    assert_eq!(to_vec(&rope), b"bcdef");
+    assert_eq!(to_vec(&snapshot), b"abc");
+}
+
+#[test]
+fn concat() {
+    let mut a = BytesRope::new();
+    for i in 0..100u8 {
+        a.insert(a.len(), Bytes::from(vec![i]));
+    }
+    let b = BytesRope::from(Bytes::from_static(b"tail"));
+
+    let rope = a.concat(b).concat(BytesRope::new());
+    assert_eq!(rope.len(), 104);
+
+    let v = to_vec(&rope);
+    assert_eq!(&v[..100], &(0..100).collect::<Vec<u8>>()[..]);
+    assert_eq!(&v[100..], b"tail");
+}
+
+#[test]
+fn matches_vec_model() {
+    // deterministic pseudo random edits
+    let mut seed = 0x2545_f491_u32;
+    let mut next = |bound: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as usize % bound
+    };
+
+    let mut rope = BytesRope::new();
+    let mut model = Vec::new();
+
+    for i in 0..2000 {
+        if next(3) == 0 && !model.is_empty() {
+            let start = next(model.len());
+            let end = start + next(model.len() - start + 1);
+            rope.remove(start..end);
+            model.drain(start..end);
+        } else {
+            let at = next(model.len() + 1);
+            let data = vec![i as u8; next(8) + 1];
+            rope.insert(at, Bytes::from(data.clone()));
+            model.splice(at..at, data);
+        }
+
+        assert_eq!(rope.len(), model.len());
+    }
+
+    assert_eq!(to_vec(&rope), model);
+
+    let start = model.len() / 3;
+    let end = model.len() / 2;
+    assert_eq!(to_vec(&rope.slice(start..end)), &model[start..end]);
+}
+
+#[test]
+fn buf() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"world"));
+    rope.insert(0, Bytes::from_static(b"hello "));
+    rope.insert(0, Bytes::from_static(b"\x00\x05"));
+
+    assert_eq!(rope.find_slice(b"o w"), Ok(Some(6)));
+    assert_eq!(rope.get_u16(), 5);
+
+    let hello = rope.copy_to_bytes(5);
+    assert_eq!(hello, &b"hello"[..]);
+    assert_eq!(rope.copy_to_bytes(3), &b" wo"[..]);
+    assert_eq!(rope.remaining(), 3);
+    assert_eq!(rope.chunk(), b"rld");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_read() {
+    let mut rope = BytesRope::from(Bytes::from_static(b"c"));
+    rope.insert(0, Bytes::from_static(b"b"));
+    rope.insert(0, Bytes::from_static(b"a"));
+
+    let mut iovecs = [IoSlice::new(&[]); 4];
+    assert_eq!(rope.chunks_vectored(&mut iovecs), 3);
+    assert_eq!(iovecs[0][..], b"a"[..]);
+    assert_eq!(iovecs[1][..], b"b"[..]);
+    assert_eq!(iovecs[2][..], b"c"[..]);
+
+    let mut iovecs = [IoSlice::new(&[]); 2];
+    assert_eq!(rope.chunks_vectored(&mut iovecs), 2);
+    assert_eq!(iovecs[1][..], b"b"[..]);
+}
@@ -1,6 +1,6 @@
 #![warn(rust_2018_idioms)]
 
-use bytes::{Buf, BufMut, Bytes};
+use bytes::{Buf, BufMut, Bytes, TryGetError};
 #[cfg(feature = "std")]
 use std::io::IoSlice;
 
@@ -175,3 +175,101 @@ fn chain_get_bytes() {
     // assert `get_bytes` did not allocate
     assert_eq!(cd_ptr.wrapping_offset(1), d.as_ptr());
 }
+
+#[test]
+fn chain_peek_across_chunks() {
+    let a = Bytes::from(&b"\x01\x02\x03"[..]);
+    let b = Bytes::from(&b"\x04\x05"[..]);
+    let mut buf = a.chain(b);
+
+    assert_eq!(Ok(0x0102_0304), buf.peek_u32());
+    assert_eq!(Ok(0x0403_0201), buf.peek_int_le(4));
+    assert_eq!(5, buf.remaining());
+
+    let mut dst = [0; 5];
+    buf.peek_slice(&mut dst).unwrap();
+    assert_eq!(dst, [1, 2, 3, 4, 5]);
+
+    buf.advance(2);
+    assert_eq!(Ok(0x03_0405), buf.peek_uint(3));
+    assert_eq!(Ok(0x0403), buf.peek_u16_le());
+    assert_eq!(3, buf.remaining());
+}
+
+#[test]
+fn chain_peek_overflow() {
+    let buf = (&b"\x01"[..]).chain(&b"\x02"[..]);
+    assert_eq!(
+        Err(TryGetError {
+            requested: 4,
+            available: 2
+        }),
+        buf.peek_u32()
+    );
+}
+
+#[test]
+fn chain_varint_across_chunks() {
+    let mut buf = (&b"\x01\xac"[..]).chain(&b"\x02\xff"[..]);
+
+    assert_eq!(Ok(1), buf.get_uvarint_u64());
+    assert_eq!(Ok(300), buf.get_uvarint_u32());
+    assert_eq!(
+        Err(bytes::buf::VarintError::Truncated),
+        buf.get_uvarint_u64()
+    );
+    assert_eq!(1, buf.remaining());
+}
+
+#[test]
+fn chain_get_prefixed_bytes() {
+    let mut buf = (&b"\x00\x00"[..]).chain(&b"\x00\x05hello world"[..]);
+
+    assert_eq!(Bytes::from_static(b"hello"), buf.get_u32_prefixed_bytes());
+    assert_eq!(6, buf.remaining());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_write() {
+    use bytes::BytesMut;
+    use std::io::{IoSliceMut, Read};
+
+    let mut header = [0u8; 4];
+    let mut body = BytesMut::with_capacity(16);
+    let mut src = &b"\x00\x00\x00\x05hello"[..];
+
+    {
+        let mut buf = (&mut header[..]).chain_mut(&mut body);
+        let mut dst = [
+            IoSliceMut::new(&mut []),
+            IoSliceMut::new(&mut []),
+            IoSliceMut::new(&mut []),
+        ];
+
+        assert_eq!(2, buf.chunks_mut_vectored(&mut dst));
+        assert_eq!(dst[0].len(), 4);
+        assert!(dst[1].len() >= 16);
+
+        let n = src.read_vectored(&mut dst).unwrap();
+        assert_eq!(n, 9);
+        unsafe { buf.advance_mut(n) };
+    }
+
+    assert_eq!(header, [0, 0, 0, 5]);
+    assert_eq!(body, &b"hello"[..]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_write_partial_first() {
+    use std::io::IoSliceMut;
+
+    // `a` has more room than its first chunk, so `b` must not be exposed
+    let mut a = Vec::with_capacity(4);
+    let mut b = [0u8; 4];
+    let mut buf = (&mut a).chain_mut(&mut b[..]);
+
+    let mut dst = [IoSliceMut::new(&mut []), IoSliceMut::new(&mut [])];
+    assert_eq!(1, buf.chunks_mut_vectored(&mut dst));
+}
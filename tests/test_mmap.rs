@@ -0,0 +1,66 @@
+#![cfg(feature = "mmap")]
+#![warn(rust_2018_idioms)]
+
+use bytes::{Buf, Bytes};
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("bytes-test-mmap-{}-{}", std::process::id(), name));
+    let mut file = File::create(&path).unwrap();
+    file.write_all(contents).unwrap();
+    path
+}
+
+#[test]
+fn map_file() {
+    let path = temp_file("map_file", b"hello world");
+    let file = File::open(&path).unwrap();
+
+    let b = unsafe { Bytes::map_file(&file).unwrap() };
+    drop(file);
+
+    assert_eq!(b, &b"hello world"[..]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn map_file_shares_mapping() {
+    let path = temp_file("shares_mapping", b"hello world");
+    let file = File::open(&path).unwrap();
+
+    let mut a = unsafe { Bytes::map_file(&file).unwrap() };
+    let ptr = a.as_ptr();
+
+    let b = a.clone();
+    let c = a.slice(6..);
+    let d = a.split_to(5);
+    a.advance(1);
+
+    assert_eq!(b.as_ptr(), ptr);
+    assert_eq!(d.as_ptr(), ptr);
+    assert_eq!(c.as_ptr(), unsafe { ptr.add(6) });
+    assert_eq!(a.as_ptr(), c.as_ptr());
+
+    drop(b);
+    drop(d);
+    drop(a);
+    assert_eq!(c, &b"world"[..]);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn map_empty_file() {
+    let path = temp_file("empty", b"");
+    let file = File::open(&path).unwrap();
+
+    let b = unsafe { Bytes::map_file(&file).unwrap() };
+    assert!(b.is_empty());
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,145 @@
+#![warn(rust_2018_idioms)]
+
+use bytes::buf::RingBuf;
+use bytes::{Buf, BufMut};
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+
+#[test]
+fn put_and_get() {
+    let mut ring = RingBuf::with_capacity(16);
+    assert!(ring.is_empty());
+
+    ring.put_u32(0xdead_beef);
+    ring.put_slice(b"hello");
+    assert_eq!(ring.len(), 9);
+    assert_eq!(ring.remaining_mut(), 7);
+
+    assert_eq!(ring.get_u32(), 0xdead_beef);
+    assert_eq!(ring.copy_to_bytes(5), &b"hello"[..]);
+    assert!(ring.is_empty());
+    assert_eq!(ring.remaining_mut(), 16);
+}
+
+#[test]
+fn wrap_around() {
+    let mut ring = RingBuf::with_capacity(8);
+    let storage = ring.chunk_mut().as_mut_ptr();
+
+    ring.put_slice(b"abcdef");
+    ring.advance(5);
+    assert_eq!(ring.remaining_mut(), 7);
+
+    // the free space is split by the end of the storage
+    assert_eq!(ring.chunk_mut().len(), 2);
+    ring.put_slice(b"ghijk");
+    assert!(!ring.is_full());
+    assert_eq!(ring.chunk_mut().len(), 2);
+    assert_eq!(ring.chunk_mut().as_mut_ptr(), unsafe { storage.add(3) });
+
+    assert_eq!(ring.as_slices(), (&b"fgh"[..], &b"ijk"[..]));
+    assert_eq!(ring.chunk(), b"fgh");
+    assert_eq!(ring.peek_u32(), Ok(u32::from_be_bytes(*b"fghi")));
+    assert_eq!(ring.find_byte(b'k'), Ok(Some(5)));
+
+    ring.put_slice(b"lm");
+    assert!(ring.is_full());
+    assert_eq!(ring.remaining_mut(), 0);
+    assert_eq!(ring.chunk_mut().len(), 0);
+
+    assert_eq!(ring.copy_to_bytes(8), &b"fghijklm"[..]);
+    assert_eq!(ring.chunk_mut().as_mut_ptr(), storage);
+    assert_eq!(ring.chunk_mut().len(), 8);
+}
+
+#[test]
+#[should_panic]
+fn put_overflow() {
+    let mut ring = RingBuf::with_capacity(4);
+    ring.put_slice(b"abc");
+    ring.advance(1);
+    ring.put_slice(b"def");
+}
+
+#[test]
+#[should_panic]
+fn advance_past_remaining() {
+    let mut ring = RingBuf::with_capacity(4);
+    ring.put_slice(b"ab");
+    ring.advance(3);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut ring = RingBuf::with_capacity(0);
+    assert!(ring.is_full());
+    assert_eq!(ring.chunk_mut().len(), 0);
+    assert_eq!(ring.chunk(), b"");
+    assert!(ring.try_put_u8(1).is_err());
+}
+
+#[test]
+fn clear() {
+    let mut ring = RingBuf::with_capacity(4);
+    ring.put_slice(b"abc");
+    ring.advance(2);
+    ring.put_slice(b"de");
+
+    ring.clear();
+    assert!(ring.is_empty());
+    assert_eq!(ring.chunk_mut().len(), 4);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_read() {
+    let mut ring = RingBuf::with_capacity(6);
+    ring.put_slice(b"abcde");
+    ring.advance(4);
+    ring.put_slice(b"fgh");
+
+    let mut iovecs = [IoSlice::new(&[]); 3];
+    assert_eq!(ring.chunks_vectored(&mut iovecs), 2);
+    assert_eq!(iovecs[0][..], b"ef"[..]);
+    assert_eq!(iovecs[1][..], b"gh"[..]);
+
+    let mut iovecs = [IoSlice::new(&[]); 1];
+    assert_eq!(ring.chunks_vectored(&mut iovecs), 1);
+    assert_eq!(iovecs[0][..], b"ef"[..]);
+
+    ring.advance(2);
+    let mut iovecs = [IoSlice::new(&[]); 3];
+    assert_eq!(ring.chunks_vectored(&mut iovecs), 1);
+    assert_eq!(iovecs[0][..], b"gh"[..]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn vectored_write() {
+    use std::io::{IoSliceMut, Read};
+
+    let mut ring = RingBuf::with_capacity(8);
+    ring.put_slice(b"abcde");
+    ring.advance(3);
+
+    let mut dst = [
+        IoSliceMut::new(&mut []),
+        IoSliceMut::new(&mut []),
+        IoSliceMut::new(&mut []),
+    ];
+    assert_eq!(ring.chunks_mut_vectored(&mut dst), 2);
+    assert_eq!(dst[0].len(), 3);
+    assert_eq!(dst[1].len(), 3);
+
+    let n = (&b"fghij"[..]).read_vectored(&mut dst).unwrap();
+    unsafe { ring.advance_mut(n) };
+    assert_eq!(ring.as_slices(), (&b"defgh"[..], &b"ij"[..]));
+
+    let mut dst = [IoSliceMut::new(&mut []), IoSliceMut::new(&mut [])];
+    assert_eq!(ring.chunks_mut_vectored(&mut dst), 1);
+    assert_eq!(dst[0].len(), 1);
+
+    ring.put_u8(b'k');
+    let mut dst = [IoSliceMut::new(&mut [])];
+    assert_eq!(ring.chunks_mut_vectored(&mut dst), 0);
+}